@@ -0,0 +1,396 @@
+use colored::Colorize;
+use spinne_core::{ComponentRegistry, ExportOptions};
+use std::{collections::BTreeMap, fmt::Write};
+
+/// What the console report shows besides the project summary
+#[derive(Debug, Clone)]
+pub struct ConsoleOptions {
+    /// How many of the most used components are listed
+    pub top: usize,
+    /// Prints the dependency tree of the components with this name or id
+    pub root: Option<String>,
+    /// Limits the depth of the dependency tree
+    pub depth: Option<usize>,
+}
+
+/// Counts of a project for the summary table
+#[derive(Default)]
+struct ProjectSummary {
+    components: usize,
+    edges: usize,
+    cross_project_edges: usize,
+    unused: usize,
+}
+
+/// Renders a human-readable report of the registry for the terminal.
+/// Fails if the root of the options doesn't match any component.
+pub fn report(registry: &ComponentRegistry, options: &ConsoleOptions) -> Result<String, String> {
+    let export = registry.export(&ExportOptions::default())?;
+    let mut output = String::new();
+
+    let dependents: Vec<usize> = export
+        .components
+        .iter()
+        .map(|info| registry.get_dependents(&info.node.id).len())
+        .collect();
+
+    // A component is unused if it's not reachable from an entry point of its project. Components
+    // exported from an entry point are part of the public API and never unused.
+    let unused: Vec<usize> = (0..export.components.len())
+        .filter(|&index| {
+            let node = &export.components[index].node;
+            !node.public_api && node.reachable == Some(false)
+        })
+        .collect();
+    // Without entry points, a component no other component renders is either a root like App or
+    // unused, so these are listed separately
+    let without_dependents: Vec<usize> = (0..export.components.len())
+        .filter(|&index| {
+            export.components[index].node.reachable.is_none() && dependents[index] == 0
+        })
+        .collect();
+
+    let mut projects: BTreeMap<&str, ProjectSummary> = BTreeMap::new();
+    for info in &export.components {
+        projects.entry(&info.project).or_default().components += 1;
+    }
+    for edge in &export.edges {
+        let summary = projects
+            .entry(&export.components[edge.from].project)
+            .or_default();
+        summary.edges += 1;
+        if edge.cross_project {
+            summary.cross_project_edges += 1;
+        }
+    }
+    for &index in &unused {
+        projects
+            .entry(&export.components[index].project)
+            .or_default()
+            .unused += 1;
+    }
+
+    section(&mut output, "Projects");
+    output.push_str(&table(
+        &[
+            "Project",
+            "Components",
+            "Edges",
+            "Cross-project edges",
+            "Unused",
+        ],
+        projects
+            .iter()
+            .map(|(project, summary)| {
+                vec![
+                    project.to_string(),
+                    summary.components.to_string(),
+                    summary.edges.to_string(),
+                    summary.cross_project_edges.to_string(),
+                    summary.unused.to_string(),
+                ]
+            })
+            .collect(),
+    ));
+
+    let mut most_used: Vec<usize> = (0..export.components.len())
+        .filter(|&index| dependents[index] > 0)
+        .collect();
+    most_used.sort_by_key(|&index| std::cmp::Reverse((export.usages[index], dependents[index])));
+    most_used.truncate(options.top);
+
+    section(&mut output, "Most used components");
+    if most_used.is_empty() {
+        let _ = writeln!(
+            output,
+            "{}",
+            "No component is rendered by another one".dimmed()
+        );
+    } else {
+        output.push_str(&table(
+            &["Component", "Project", "Usages", "Dependents"],
+            most_used
+                .iter()
+                .map(|&index| {
+                    let info = export.components[index];
+                    vec![
+                        info.node.name.clone(),
+                        info.project.clone(),
+                        export.usages[index].to_string(),
+                        dependents[index].to_string(),
+                    ]
+                })
+                .collect(),
+        ));
+    }
+
+    section(&mut output, "Unused components");
+    if unused.is_empty() {
+        let _ = writeln!(output, "{}", "None".dimmed());
+    }
+    for &index in &unused {
+        let info = export.components[index];
+        let _ = writeln!(
+            output,
+            "{}  {}",
+            info.node.name.yellow(),
+            info.node.file_path.display().to_string().dimmed()
+        );
+    }
+
+    if !without_dependents.is_empty() {
+        section(&mut output, "Components without dependents");
+        let _ = writeln!(
+            output,
+            "{}",
+            "Roots or unused components of projects without entry points".dimmed()
+        );
+        for &index in &without_dependents {
+            let info = export.components[index];
+            let _ = writeln!(
+                output,
+                "{}  {}",
+                info.node.name,
+                info.node.file_path.display().to_string().dimmed()
+            );
+        }
+    }
+
+    if let Some(root) = &options.root {
+        let roots: Vec<_> = export
+            .components
+            .iter()
+            .filter(|info| info.node.name == *root || info.node.id == *root)
+            .collect();
+        if roots.is_empty() {
+            return Err(format!("No component named {}", root));
+        }
+
+        for info in roots {
+            section(
+                &mut output,
+                &format!("Dependencies of {} ({})", info.node.name, info.project),
+            );
+            output.push_str(&tree(registry, &info.node.id, options.depth));
+        }
+    }
+
+    Ok(output)
+}
+
+/// Writes the title of a section
+fn section(output: &mut String, title: &str) {
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    let _ = writeln!(output, "{}", title.blue().bold());
+}
+
+/// Renders rows as a table with left aligned text and right aligned numbers
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let numeric: Vec<bool> = (0..headers.len())
+        .map(|column| {
+            !rows.is_empty() && rows.iter().all(|row| row[column].parse::<usize>().is_ok())
+        })
+        .collect();
+
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| {
+                if numeric[column] {
+                    format!("{:>width$}", cell, width = widths[column])
+                } else {
+                    format!("{:<width$}", cell, width = widths[column])
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut table = String::new();
+    let header = line(headers.iter().map(|header| header.to_string()).collect());
+    let _ = writeln!(table, "{}", header.bold());
+    for row in rows {
+        let _ = writeln!(table, "{}", line(row));
+    }
+    table
+}
+
+/// Renders the dependencies of a component as a tree. Every component is shown once, below the
+/// first component rendering it.
+fn tree(registry: &ComponentRegistry, root_id: &str, depth: Option<usize>) -> String {
+    let nodes: Vec<_> = registry
+        .traverse_from(root_id)
+        .into_iter()
+        .filter(|node| depth.is_none_or(|depth| node.depth <= depth))
+        .collect();
+
+    let mut output = String::new();
+    // whether the component at each depth of the current branch is the last of its siblings
+    let mut last_at_depth: Vec<bool> = Vec::new();
+    // the component at each depth of the current branch
+    let mut branch: Vec<&str> = Vec::new();
+
+    for (index, node) in nodes.iter().enumerate() {
+        let is_last = !matches!(
+            nodes[index + 1..].iter().find(|next| next.depth <= node.depth),
+            Some(next) if next.depth == node.depth
+        );
+        last_at_depth.truncate(node.depth);
+        last_at_depth.push(is_last);
+        branch.truncate(node.depth);
+        branch.push(&node.component_id);
+
+        if node.depth == 0 {
+            let _ = writeln!(output, "{}", node.component_name.bold());
+            continue;
+        }
+
+        for &last in &last_at_depth[1..node.depth] {
+            output.push_str(if last { "    " } else { "│   " });
+        }
+        output.push_str(if is_last { "└── " } else { "├── " });
+        output.push_str(&node.component_name);
+
+        let parent = branch[node.depth - 1];
+        let edge = registry
+            .get_dependencies(parent)
+            .into_iter()
+            .find(|(id, _)| *id == node.component_id)
+            .map(|(_, edge)| edge);
+        if let Some(edge) = edge.filter(|edge| edge.count > 1) {
+            let _ = write!(output, " ×{}", edge.count);
+        }
+        let parent_project = registry
+            .get_component(parent)
+            .map(|info| info.project.as_str());
+        if parent_project != Some(node.project.as_str()) {
+            let _ = write!(output, " {}", format!("({})", node.project).dimmed());
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spinne_core::{ComponentReport, EdgeReport, GraphReport, ProjectReport, Report};
+
+    /// App renders Page and Nav, Page renders Card and Button from another project,
+    /// Card renders Button as well. Legacy can't be reached from App.
+    fn registry() -> ComponentRegistry {
+        let component = |id: &str, reachable: Option<bool>| ComponentReport {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: format!("src/{}.tsx", id).into(),
+            reachable,
+            ..ComponentReport::default()
+        };
+        let edge = |from: &str, to: &str, count: usize, project: &str| EdgeReport {
+            from: from.to_string(),
+            to: to.to_string(),
+            project_context: Some(project.to_string()),
+            count,
+            ..EdgeReport::default()
+        };
+
+        let app = GraphReport {
+            components: ["App", "Page", "Nav", "Card"]
+                .into_iter()
+                .map(|id| component(id, Some(true)))
+                .chain([component("Legacy", Some(false))])
+                .collect(),
+            edges: vec![
+                edge("App", "Page", 1, "app"),
+                edge("App", "Nav", 1, "app"),
+                edge("Page", "Card", 2, "app"),
+                edge("Page", "Button", 1, "ui"),
+                edge("Card", "Button", 3, "ui"),
+            ],
+            ..GraphReport::default()
+        };
+        let ui = GraphReport {
+            components: vec![component("Button", None)],
+            ..GraphReport::default()
+        };
+
+        ComponentRegistry::from_report(&Report::new(vec![
+            ProjectReport {
+                name: "app".to_string(),
+                graph: app,
+            },
+            ProjectReport {
+                name: "ui".to_string(),
+                graph: ui,
+            },
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_report() {
+        colored::control::set_override(false);
+        let registry = registry();
+        let options = ConsoleOptions {
+            top: 2,
+            root: Some("App".to_string()),
+            depth: None,
+        };
+
+        assert_eq!(
+            report(&registry, &options).unwrap(),
+            "\
+Projects
+Project  Components  Edges  Cross-project edges  Unused
+app               5      5                    2       1
+ui                1      0                    0       0
+
+Most used components
+Component  Project  Usages  Dependents
+Button     ui            4           2
+Card       app           2           1
+
+Unused components
+Legacy  src/Legacy.tsx
+
+Dependencies of App (app)
+App
+├── Nav
+└── Page
+    ├── Button (ui)
+    └── Card ×2
+"
+        );
+
+        let options = ConsoleOptions {
+            depth: Some(1),
+            ..options
+        };
+        assert!(report(&registry, &options)
+            .unwrap()
+            .ends_with("App\n├── Nav\n└── Page\n"));
+
+        let options = ConsoleOptions {
+            root: Some("Missing".to_string()),
+            ..options
+        };
+        assert!(report(&registry, &options).is_err());
+    }
+}
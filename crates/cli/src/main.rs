@@ -1,52 +1,157 @@
+mod console;
+
 use clap::Parser;
 use spinne_logger::Logger;
-use std::{fs::File, io::{BufWriter, Write}, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
 
-use spinne_core::Workspace;
+use spinne_core::{
+    Baseline, ComponentRegistry, Config, ExportOptions, Report, Severity, Workspace,
+};
 use spinne_html::HtmlGenerator;
 
+use console::ConsoleOptions;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
     /// Entry point directory
     ///
     /// This is only the starting point of the analysis and spinne will traverse all projects in this directory.
-    #[arg(short, long, default_value = "./")]
+    #[arg(short, long, default_value = "./", global = true)]
     entry: PathBuf,
 
     /// Output format for the report
     ///
     /// - file: Outputs a JSON file (spinne-report.json) containing the component graph.
     ///   The JSON structure includes:
-    ///   - An array of project objects, each containing:
+    ///   - schema_version: The version of the report format
+    ///   - projects: An array of project objects, each containing:
     ///     - name: The project name
     ///     - graph: A component graph containing:
     ///       - components: Array of component objects with id, name, path, props, and project
     ///       - edges: Array of edge objects with from and to component IDs
     ///
-    /// - console: Prints the report directly to the console in a human-readable format
+    ///   The format is described by the JSON Schema printed by `spinne schema`.
+    ///
+    /// - console: Prints a human-readable report to the console with a summary of each project,
+    ///   the most used and the unused components, and the dependency tree of `--root`
     ///
     /// - html: Generates an interactive HTML report (spinne-report.html)
     ///
     /// - json: Outputs raw JSON to stdout (useful for piping to other commands)
+    ///
+    /// - dot: Writes the component graph in the Graphviz DOT language (spinne-report.dot),
+    ///   with a cluster per project
+    ///
+    /// - mermaid: Writes the component graph as a Mermaid flowchart (spinne-report.mmd),
+    ///   with a subgraph per project
+    ///
+    /// - graphml: Writes the component graph as GraphML (spinne-report.graphml), e.g. for yEd
+    ///   or NetworkX
+    ///
+    /// - gexf: Writes the component graph as GEXF (spinne-report.gexf), e.g. for Gephi
     #[arg(short, long, default_value = "file")]
     format: Format,
 
+    /// Only output the components rendered by the component with this name or id
+    /// (dot, mermaid, graphml and gexf). The console report prints their dependency tree.
+    #[arg(long)]
+    root: Option<String>,
+
+    /// Only output components up to this many dependencies away from the root
+    /// (dot, mermaid, graphml, gexf and the dependency tree of console)
+    #[arg(long)]
+    depth: Option<usize>,
+
+    /// Number of the most used components listed in the console report
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    /// Print without colors
+    #[arg(long, global = true)]
+    no_color: bool,
+
     /// Exclude directories/files with glob patterns (comma separated)
     #[arg(
         long,
         value_delimiter = ',',
-        default_value = "**/node_modules/**,**/dist/**,**/build/**,**/*.stories.tsx,**/*.test.tsx"
+        default_value = "**/node_modules/**,**/dist/**,**/build/**,**/*.d.ts,**/*.stories.*,**/*.test.*"
     )]
     exclude: Vec<String>,
 
     /// Include directories/files with glob patterns (comma separated)
-    #[arg(long, value_delimiter = ',', default_value = "**/*.tsx")]
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "**/*.tsx,**/*.ts,**/*.jsx,**/*.js,**/*.mjs,**/*.cjs,**/*.mts,**/*.cts"
+    )]
     include: Vec<String>,
 
+    /// Number of threads used to analyze files, 0 uses all available cores
+    #[arg(short, long, default_value_t = 0)]
+    jobs: usize,
+
+    /// Analyze every file again instead of reusing the results of unchanged files from `.spinne-cache`
+    #[arg(long)]
+    no_cache: bool,
+
     /// Verbosity level (-l = level 1, -ll = level 2, etc.)
-    #[arg(short = 'l', action = clap::ArgAction::Count)]
+    #[arg(short = 'l', action = clap::ArgAction::Count, global = true)]
     verbosity: u8,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Manage the analysis cache in `.spinne-cache`
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Checks the components against the `rules` in `spinne.json` of the entry directory.
+    /// Exits with a non-zero code if a rule is violated.
+    Check {
+        /// Violations recorded in this file are accepted, so only new violations fail the check
+        #[arg(long, default_value = "spinne-baseline.json")]
+        baseline: PathBuf,
+
+        /// Records all current violations in the baseline file instead of checking them
+        #[arg(long)]
+        update_baseline: bool,
+    },
+    /// Prints the JSON Schema of the report written by `--format file` and `--format json`
+    Schema,
+    /// Compares two JSON reports, e.g. of the main branch and a pull request
+    Diff {
+        /// The report of the earlier run
+        old: PathBuf,
+
+        /// The report of the later run
+        new: PathBuf,
+
+        /// Output format of the changes
+        ///
+        /// - markdown: Prints tables of the changes, e.g. for a comment on a pull request
+        ///
+        /// - json: Prints the changes as JSON
+        ///
+        /// - html: Generates an HTML report (spinne-diff.html) highlighting the changes
+        #[arg(short, long, default_value = "markdown")]
+        format: DiffFormat,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum CacheAction {
+    /// Removes the analysis cache of the entry directory
+    Clear,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum, Debug)]
@@ -59,23 +164,118 @@ enum Format {
     Html,
     /// Outputs raw JSON to stdout (useful for piping to other commands)
     Json,
+    /// Writes the component graph in the Graphviz DOT language
+    Dot,
+    /// Writes the component graph as a Mermaid flowchart
+    Mermaid,
+    /// Writes the component graph as GraphML
+    Graphml,
+    /// Writes the component graph as GEXF
+    Gexf,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum, Debug)]
+enum DiffFormat {
+    /// Prints tables of the changes
+    Markdown,
+    /// Prints the changes as JSON
+    Json,
+    /// Generates an HTML report highlighting the changes
+    Html,
 }
 
 const FILE_NAME: &str = "spinne-report";
+const DIFF_FILE_NAME: &str = "spinne-diff";
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
 
     Logger::set_level(args.verbosity);
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    if let Some(Command::Schema) = &args.command {
+        println!("{}", serde_json::to_string_pretty(&Report::json_schema())?);
+        return Ok(());
+    }
+
+    if let Some(Command::Diff { old, new, format }) = &args.command {
+        return diff(old, new, *format);
+    }
 
     let absolute_entry = std::fs::canonicalize(&args.entry)?;
 
-    let mut workspace = Workspace::new(absolute_entry);
+    let mut workspace = Workspace::new(absolute_entry.clone());
+
+    if let Some(Command::Cache {
+        action: CacheAction::Clear,
+    }) = args.command
+    {
+        workspace.clear_cache()?;
+        Logger::info("Cache cleared");
+        return Ok(());
+    }
+
+    workspace.set_jobs(args.jobs);
+    if !args.no_cache {
+        workspace.enable_cache();
+    }
     workspace.discover_projects();
     workspace.traverse_projects(&args.exclude, &args.include);
 
     // Get the shared component registry
     let registry = workspace.get_component_registry();
+
+    if let Some(Command::Check {
+        baseline,
+        update_baseline,
+    }) = &args.command
+    {
+        let passed = check(
+            registry,
+            &workspace.get_project_paths(),
+            &absolute_entry,
+            baseline,
+            *update_baseline,
+        )?;
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    // output the component graph in a graph language to a file in current working directory
+    if matches!(
+        args.format,
+        Format::Dot | Format::Mermaid | Format::Graphml | Format::Gexf
+    ) {
+        let options = ExportOptions {
+            root: args.root.clone(),
+            depth: args.depth,
+        };
+        let export = match registry.export(&options) {
+            Ok(export) => export,
+            Err(e) => {
+                Logger::error(&e);
+                std::process::exit(1);
+            }
+        };
+
+        let (extension, content) = match args.format {
+            Format::Dot => ("dot", export.to_dot()),
+            Format::Mermaid => ("mmd", export.to_mermaid()),
+            Format::Graphml => ("graphml", export.to_graphml()),
+            _ => ("gexf", export.to_gexf()),
+        };
+        let output_path_with_extension =
+            std::env::current_dir()?.join(format!("{}.{}", FILE_NAME, extension));
+
+        Logger::info(&format!(
+            "Writing report to: {:?}",
+            output_path_with_extension
+        ));
+        std::fs::write(output_path_with_extension, content)?;
+        return Ok(());
+    }
+
     let serializable_data = registry.to_serializable();
 
     // output to json file in current working directory
@@ -96,8 +296,21 @@ fn main() -> std::io::Result<()> {
 
     // output to console
     if args.format == Format::Console {
-        Logger::info("Printing report to console:");
-        println!("{:#?}", serializable_data);
+        let options = ConsoleOptions {
+            top: args.top,
+            root: args.root.clone(),
+            depth: args.depth,
+        };
+        match console::report(registry, &options) {
+            Ok(report) => {
+                Logger::info("Printing report to console:");
+                print!("{}", report);
+            }
+            Err(e) => {
+                Logger::error(&e);
+                std::process::exit(1);
+            }
+        }
     }
 
     // output raw JSON to stdout
@@ -120,9 +333,7 @@ fn main() -> std::io::Result<()> {
             output_path_with_extension
         ));
 
-        match HtmlGenerator::new(serializable_data.clone())
-            .save(&output_path_with_extension)
-        {
+        match HtmlGenerator::new(serializable_data.clone()).save(&output_path_with_extension) {
             Ok(_) => Logger::info(&format!(
                 "Report written to: {:?}",
                 output_path_with_extension
@@ -139,3 +350,106 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// Checks the registry against the rules of the entry directory and prints the violations.
+/// Returns whether the check passed.
+fn check(
+    registry: &ComponentRegistry,
+    project_paths: &HashMap<String, PathBuf>,
+    entry: &Path,
+    baseline_path: &Path,
+    update_baseline: bool,
+) -> std::io::Result<bool> {
+    let config_path = entry.join("spinne.json");
+    let Some(rules) = Config::read(config_path.clone()).and_then(|config| config.rules) else {
+        Logger::error(&format!("No rules configured in {:?}", config_path));
+        return Ok(false);
+    };
+
+    let violations = rules.check(registry, project_paths);
+
+    if update_baseline {
+        Baseline::from_violations(&violations).save(baseline_path)?;
+        Logger::info(&format!(
+            "Wrote {} violations to baseline {:?}",
+            violations.len(),
+            baseline_path
+        ));
+        return Ok(true);
+    }
+
+    let baseline = if baseline_path.exists() {
+        Baseline::read(baseline_path)?
+    } else {
+        Baseline::default()
+    };
+    let total = violations.len();
+    let violations = baseline.filter(violations);
+
+    for violation in &violations {
+        match violation.severity {
+            Severity::Error => Logger::error(&violation.to_string()),
+            Severity::Warning => Logger::warn(&violation.to_string()),
+        }
+    }
+
+    let errors = violations
+        .iter()
+        .filter(|violation| violation.severity == Severity::Error)
+        .count();
+    let summary = format!(
+        "Found {} errors and {} warnings ({} accepted by the baseline)",
+        errors,
+        violations.len() - errors,
+        total - violations.len()
+    );
+    if errors > 0 {
+        Logger::error(&summary);
+    } else {
+        Logger::info(&summary);
+    }
+
+    Ok(errors == 0)
+}
+
+/// Reads a JSON report written by an earlier run
+fn read_report(path: &Path) -> std::io::Result<ComponentRegistry> {
+    let content = std::fs::read_to_string(path)?;
+    let value = serde_json::from_str(&content)?;
+
+    ComponentRegistry::from_serializable(&value).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Invalid report {:?}: {}", path, e),
+        )
+    })
+}
+
+/// Compares two reports and outputs the changes
+fn diff(old: &Path, new: &Path, format: DiffFormat) -> std::io::Result<()> {
+    let diff = read_report(old)?.diff(&read_report(new)?);
+
+    match format {
+        DiffFormat::Markdown => print!("{}", diff.to_markdown()),
+        DiffFormat::Json => {
+            let stdout = std::io::stdout();
+            let mut writer = BufWriter::new(stdout.lock());
+            serde_json::to_writer(&mut writer, &diff.to_serializable())?;
+            writer.flush()?;
+        }
+        DiffFormat::Html => {
+            let output_path = std::env::current_dir()?.join(format!("{}.html", DIFF_FILE_NAME));
+
+            HtmlGenerator::diff(diff.to_serializable()).save(&output_path)?;
+            Logger::info(&format!("Report written to: {:?}", output_path));
+
+            #[cfg(not(test))]
+            match open::that_detached(output_path) {
+                Ok(_) => Logger::info("Opened report in browser"),
+                Err(e) => Logger::error(&format!("Failed to open report in browser: {}", e)),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -214,8 +214,8 @@ fn test_cli_with_json_output() {
     let json: Value = serde_json::from_str(&json_content).unwrap();
 
     // Verify JSON structure
-    assert!(json.is_array());
-    let project = &json[0];
+    assert_eq!(json["schema_version"], 1);
+    let project = &json["projects"][0];
     assert_eq!(project["name"], "mock-project");
 
     let graph = &project["graph"];
@@ -292,6 +292,502 @@ fn test_cli_with_json_stdout() {
     let last_line = stdout.lines().last().unwrap_or("");
     let json: Value = serde_json::from_str(last_line).unwrap();
 
-    assert!(json.is_array());
-    assert_eq!(json[0]["name"], "mock-project");
+    assert_eq!(json["schema_version"], 1);
+    assert_eq!(json["projects"][0]["name"], "mock-project");
+}
+
+#[test]
+fn test_cli_analyzes_javascript_files() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        ("src/components/Button.jsx", "export const Button = ({ label }) => { return <button>{label}</button>; }"),
+        ("src/pages/Home.js", "import { Button } from '../components/Button'; export const Home = () => { return <main><Button label=\"Go\" /></main>; }"),
+        ("src/components/Button.stories.jsx", "export const ButtonStory = () => { return <div />; }"),
+    ]);
+    let mut cmd = Command::cargo_bin("spinne").unwrap();
+
+    cmd.current_dir(temp_dir.path())
+        .arg("-f")
+        .arg("console")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Button"))
+        .stdout(predicate::str::contains("Home"))
+        .stdout(predicate::str::contains("ButtonStory").not());
+}
+
+#[test]
+fn test_cli_reuses_and_clears_cache() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        (
+            "src/components/Button.tsx",
+            "export const Button = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/pages/Home.tsx",
+            "import { Button } from '../components/Button'; export const Home = () => { return <Button />; }",
+        ),
+    ]);
+    let cache_dir = temp_dir.path().join(".spinne-cache");
+    let run = |args: &[&str]| {
+        let output = Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let json: Value = serde_json::from_str(stdout.lines().last().unwrap_or("")).unwrap();
+        let mut components: Vec<String> = json["projects"][0]["graph"]["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|component| component["name"].as_str().unwrap().to_string())
+            .collect();
+        components.sort();
+        components
+    };
+
+    run(&["--no-cache", "-f", "json"]);
+    assert!(!cache_dir.exists());
+
+    let cold = run(&["-f", "json"]);
+    assert!(cache_dir.exists());
+    let warm = run(&["-f", "json", "-ll"]);
+    assert_eq!(cold, warm);
+    assert_eq!(warm, vec!["Button", "Home"]);
+
+    Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["cache", "clear"])
+        .assert()
+        .success();
+    assert!(!cache_dir.exists());
+}
+
+#[test]
+fn test_cli_cache_follows_changed_reexports() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        ("src/A.tsx", "export const Primary = () => <button>A</button>;"),
+        ("src/B.tsx", "export const Secondary = () => <a>B</a>;"),
+        ("src/ui.ts", "export { Primary as Button } from './A';"),
+        (
+            "src/App.tsx",
+            "import { Button } from './ui'; import { Card } from './Card'; export const App = () => <><Button /><Card /></>;",
+        ),
+    ]);
+    let run = |args: &[&str]| {
+        let output = Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let json: Value = serde_json::from_str(stdout.lines().last().unwrap_or("")).unwrap();
+        json["projects"][0]["graph"].clone()
+    };
+    let paths = |graph: &Value| -> Vec<String> {
+        let mut paths: Vec<String> = graph["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|component| {
+                graph["edges"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|edge| edge["to"] == component["id"])
+            })
+            .map(|component| component["path"].as_str().unwrap().to_string())
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    let cold = run(&["-f", "json"]);
+    assert_eq!(paths(&cold), vec!["./Card", "mock-project/src/A.tsx"]);
+
+    // App.tsx is unchanged, but the barrel it imports from and the missing file are not
+    fs::write(
+        temp_dir.path().join("src/ui.ts"),
+        "export { Secondary as Button } from './B';",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("src/Card.tsx"),
+        "export const Card = () => <div />;",
+    )
+    .unwrap();
+
+    let warm = run(&["-f", "json"]);
+    assert_eq!(warm, run(&["--no-cache", "-f", "json"]));
+    assert_eq!(
+        paths(&warm),
+        vec!["mock-project/src/B.tsx", "mock-project/src/Card.tsx"]
+    );
+}
+
+#[test]
+fn test_cli_check() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        (
+            "spinne.json",
+            r#"{
+                "rules": {
+                    "components": [{ "name": "OldButton", "replacement": "Button" }],
+                    "intrinsic_elements": [{ "element": "main", "severity": "warning" }]
+                }
+            }"#,
+        ),
+        (
+            "src/components/OldButton.tsx",
+            "export const OldButton = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/pages/Home.tsx",
+            "import { OldButton } from '../components/OldButton';\nexport const Home = () => { return <main><OldButton /></main>; }",
+        ),
+    ]);
+    let check = |args: &[&str]| {
+        Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .arg("check")
+            .args(args)
+            .assert()
+    };
+
+    check(&[])
+        .failure()
+        .stderr(predicate::str::contains(
+            "src/pages/Home.tsx:2:42: error[component] `OldButton` is banned, use Button instead",
+        ))
+        // paths are relative to the workspace root, not prefixed with the project name
+        .stderr(predicate::str::contains("mock-project/").not())
+        .stdout(predicate::str::contains(
+            "src/pages/Home.tsx:2:36: warning[intrinsic_element] `<main>` is deprecated",
+        ));
+
+    check(&["--update-baseline"]).success();
+    assert!(temp_dir.path().join("spinne-baseline.json").exists());
+
+    check(&[]).success().stdout(predicate::str::contains(
+        "Found 0 errors and 0 warnings (2 accepted by the baseline)",
+    ));
+}
+
+#[test]
+fn test_cli_check_without_rules() {
+    let temp_dir = create_mock_project(&vec![(
+        "src/components/Button.tsx",
+        "export const Button = () => { return <button>Click me</button>; }",
+    )]);
+
+    Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No rules configured"));
+}
+
+#[test]
+fn test_cli_diff() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        (
+            "src/components/Button.tsx",
+            "export const Button = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/pages/Home.tsx",
+            "import { Button } from '../components/Button'; export const Home = () => { return <Button />; }",
+        ),
+    ]);
+    let report = |name: &str| {
+        Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .args(["--no-cache", "-f", "file"])
+            .assert()
+            .success();
+        fs::rename(
+            temp_dir.path().join("spinne-report.json"),
+            temp_dir.path().join(name),
+        )
+        .unwrap();
+    };
+
+    report("old.json");
+    fs::write(
+        temp_dir.path().join("src/pages/Home.tsx"),
+        "import { Button } from '../components/Button'; import { Card } from '../components/Card'; export const Home = () => { return <Card><Button variant=\"primary\" /></Card>; }",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("src/components/Card.tsx"),
+        "export const Card = ({ children }) => { return <div>{children}</div>; }",
+    )
+    .unwrap();
+    report("new.json");
+
+    let diff = |format: &str| {
+        Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .args(["diff", "old.json", "new.json", "-f", format])
+            .output()
+            .unwrap()
+    };
+
+    let output = diff("json");
+    assert!(output.status.success());
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["added_components"][0]["name"], "Card");
+    assert_eq!(json["summary"]["removed_components"], 0);
+    assert_eq!(json["summary"]["added_edges"], 1);
+    assert_eq!(json["prop_changes"][0]["prop"], "variant");
+
+    let output = diff("markdown");
+    let markdown = String::from_utf8(output.stdout).unwrap();
+    assert!(markdown.contains("### Added components"));
+    assert!(markdown.contains("| `Button` (mock-project) | `variant` | 0 | 1 | +1 |"));
+
+    diff("html");
+    assert!(temp_dir.path().join("spinne-diff.html").exists());
+
+    Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["diff", "old.json", "missing.json"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_cli_schema() {
+    let output = Command::cargo_bin("spinne")
+        .unwrap()
+        .arg("schema")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let schema: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["title"], "Report");
+    assert!(schema["required"]
+        .as_array()
+        .unwrap()
+        .contains(&Value::from("schema_version")));
+}
+
+#[test]
+fn test_cli_report_is_deterministic() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        (
+            "src/components/Button.tsx",
+            "export const Button = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/components/Card.tsx",
+            "import { Button } from './Button'; export const Card = () => { return <div><Button variant=\"primary\" size=\"lg\" /></div>; }",
+        ),
+        (
+            "src/pages/Home.tsx",
+            "import { Button } from '../components/Button'; import { Card } from '../components/Card'; export const Home = () => { return <main><Card /><Button label=\"a\" disabled /></main>; }",
+        ),
+    ]);
+    let report = || {
+        Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .args(["--no-cache", "-f", "json"])
+            .output()
+            .unwrap()
+            .stdout
+    };
+
+    let first = report();
+    assert!(!first.is_empty());
+    for _ in 0..3 {
+        assert_eq!(report(), first);
+    }
+}
+
+#[test]
+fn test_cli_graph_formats() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        (
+            "src/components/Button.tsx",
+            "export const Button = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/components/Card.tsx",
+            "import { Button } from './Button'; export const Card = () => { return <div><Button /></div>; }",
+        ),
+        (
+            "src/pages/Home.tsx",
+            "import { Card } from '../components/Card'; export const Home = () => { return <main><Card /></main>; }",
+        ),
+    ]);
+    let graph = |args: &[&str], file: &str| {
+        Command::cargo_bin("spinne")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .arg("--no-cache")
+            .args(args)
+            .assert()
+            .success();
+        fs::read_to_string(temp_dir.path().join(file)).unwrap()
+    };
+
+    let dot = graph(&["-f", "dot"], "spinne-report.dot");
+    assert!(dot.contains("digraph components {"));
+    assert!(dot.contains("label=\"mock-project\";"));
+    assert!(dot.contains("[label=\"Home\""));
+    assert_eq!(dot.matches(" -> ").count(), 2);
+    assert!(!dot.contains("style=dashed"));
+
+    let mermaid = graph(
+        &["-f", "mermaid", "--root", "Home", "--depth", "1"],
+        "spinne-report.mmd",
+    );
+    assert!(mermaid.starts_with("flowchart LR\n"));
+    assert!(mermaid.contains("[\"Home\"]"));
+    assert!(mermaid.contains("[\"Card\"]"));
+    assert!(!mermaid.contains("[\"Button\"]"));
+    assert_eq!(mermaid.matches(" --> ").count(), 1);
+
+    let graphml = graph(&["-f", "graphml"], "spinne-report.graphml");
+    assert!(graphml.contains("<graphml"));
+    assert_eq!(graphml.matches("<node ").count(), 3);
+    assert_eq!(graphml.matches("<edge ").count(), 2);
+
+    let gexf = graph(&["-f", "gexf", "--root", "Card"], "spinne-report.gexf");
+    assert!(gexf.contains("<gexf"));
+    assert!(gexf.contains("label=\"Card\""));
+    assert!(gexf.contains("label=\"Button\""));
+    assert!(!gexf.contains("label=\"Home\""));
+    assert!(gexf.contains("weight=\"1\""));
+
+    Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--no-cache", "-f", "dot", "--root", "Missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No component named Missing"));
+}
+
+#[test]
+fn test_cli_console_report() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        (
+            "src/components/Button.tsx",
+            "export const Button = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/components/Card.tsx",
+            "import { Button } from './Button'; export const Card = () => { return <div><Button /><Button /></div>; }",
+        ),
+        (
+            "src/pages/Home.tsx",
+            "import { Card } from '../components/Card'; export const Home = () => { return <main><Card /></main>; }",
+        ),
+    ]);
+    let output = Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("CLICOLOR_FORCE", "1")
+        .args([
+            "--no-cache",
+            "--no-color",
+            "-f",
+            "console",
+            "--root",
+            "Home",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains('\u{1b}'));
+    assert!(stdout.contains(
+        "Project       Components  Edges  Cross-project edges  Unused\nmock-project           3      2                    0       0\n"
+    ));
+    assert!(stdout.contains("Button     mock-project       2           1\n"));
+    // without entry points Home is not reported as unused
+    assert!(stdout.contains("Unused components\nNone\n"));
+    assert!(stdout.contains(
+        "Components without dependents\nRoots or unused components of projects without entry points\nHome  mock-project/src/pages/Home.tsx\n"
+    ));
+    assert!(stdout.ends_with("Home\n└── Card\n    └── Button ×2\n"));
+
+    Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--no-cache", "-f", "console", "--root", "Missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No component named Missing"));
+}
+
+#[test]
+fn test_cli_console_report_with_entry_points() {
+    let temp_dir = create_mock_project(&vec![
+        (".git/HEAD", "ref: refs/heads/main"),
+        ("package.json", r#"{"name": "mock-project"}"#),
+        ("spinne.json", r#"{"entry_points": ["src/index.tsx"]}"#),
+        (
+            "src/index.tsx",
+            "import { App } from './App'; createRoot(document.body).render(<App />);",
+        ),
+        (
+            "src/App.tsx",
+            "import { Button } from './Button'; export const App = () => { return <Button />; }",
+        ),
+        (
+            "src/Button.tsx",
+            "export const Button = () => { return <button>Click me</button>; }",
+        ),
+        (
+            "src/Legacy.tsx",
+            "export const Legacy = () => { return <div>Legacy</div>; }",
+        ),
+    ]);
+    let output = Command::cargo_bin("spinne")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--no-cache", "--no-color", "-f", "console"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // App is a root of the project, only Legacy is unused
+    assert!(stdout.contains("Unused components\nLegacy  mock-project/src/Legacy.tsx\n"));
+    assert!(!stdout.contains("App  mock-project/src/App.tsx"));
+    assert!(!stdout.contains("Components without dependents"));
 }
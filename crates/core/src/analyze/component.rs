@@ -1,21 +1,229 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::BTreeMap, path::PathBuf};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// A prop declared in a component's signature, e.g. `label: string` in `ButtonProps`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DeclaredProp {
+    pub name: String,
+    pub optional: bool,
+    /// The TypeScript type as written in the source, if the prop is typed
+    #[serde(rename = "type")]
+    pub type_annotation: Option<String>,
+}
+
+/// A position in a source file. Both line and column start at 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceLocation {
+    /// Computes the line and column of a byte offset in the source, e.g. the start of a span
+    pub fn from_offset(source_text: &str, offset: u32) -> Self {
+        let offset = (offset as usize).min(source_text.len());
+        let before = &source_text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// A spread attribute like `{...props}` on a rendered component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PropSpread {
+    /// The spread expression as written, e.g. `props` or `rest.buttonProps`
+    pub source: String,
+    /// Whether all keys of the spread are known. Known keys are counted as props.
+    pub resolved: bool,
+}
+
+/// A single place where a component is rendered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentUsage {
+    #[serde(flatten)]
+    pub location: SourceLocation,
+    /// The props passed at this place, sorted by name
+    #[serde(default)]
+    pub props: Vec<String>,
+    pub spreads: Vec<PropSpread>,
+    /// Whether the usage spreads props whose keys could not be determined
+    pub has_unknown_spread: bool,
+}
+
+/// The kind of value passed to a prop
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PropValueKind {
+    String,
+    Number,
+    Boolean,
+    /// An enum-like member expression, e.g. `Variant.Primary`
+    Enum,
+    Template,
+    /// Any other expression, e.g. a variable or a function call
+    Dynamic,
+}
+
+/// Statistics about the values passed to a single prop
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PropValueStats {
+    /// How often each value is passed. Values that are not known statically are counted as `<dynamic>`.
+    pub values: BTreeMap<String, usize>,
+    /// How often each kind of value is passed
+    pub kinds: BTreeMap<PropValueKind, usize>,
+}
+
+impl PropValueStats {
+    /// The value recorded for values that are not known statically
+    pub const DYNAMIC_VALUE: &'static str = "<dynamic>";
+
+    /// Records a single value passed to the prop
+    pub fn add(&mut self, kind: PropValueKind, value: &str) {
+        *self.values.entry(value.to_string()).or_insert(0) += 1;
+        *self.kinds.entry(kind).or_insert(0) += 1;
+    }
+
+    /// Adds the counts of another set of statistics
+    pub fn merge(&mut self, other: &PropValueStats) {
+        for (value, count) in &other.values {
+            *self.values.entry(value.clone()).or_insert(0) += count;
+        }
+        for (kind, count) in &other.kinds {
+            *self.kinds.entry(*kind).or_insert(0) += count;
+        }
+    }
+}
+
+/// How a component is defined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    /// A function or arrow function component
+    #[default]
+    Function,
+    /// A class extending `React.Component` or `React.PureComponent`
+    Class,
+}
+
+impl ComponentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentKind::Function => "function",
+            ComponentKind::Class => "class",
+        }
+    }
+}
+
+/// A React API that wraps a component, e.g. `React.memo(...)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentWrapper {
+    Memo,
+    ForwardRef,
+    Lazy,
+}
+
+/// Where a rendered element comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElementSource {
+    /// A native HTML or SVG element like `<div>`
+    Intrinsic,
+    /// A component of the same project
+    Local,
+    /// A component of another project in the workspace
+    Workspace,
+    /// A component of an installed package
+    ThirdParty,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentChild {
     pub name: String,
-    pub props: HashMap<String, usize>,
+    pub props: BTreeMap<String, usize>,
+    /// The values passed to each prop
+    pub prop_values: BTreeMap<String, PropValueStats>,
     pub origin_file_path: PathBuf,
     /// The name of the project this component belongs to, derived from the package.json name field
     pub project_name: Option<String>,
+    /// The component a member expression like `<Tabs.Panel />` or a default or aliased import
+    /// resolves to, if it could be found
+    pub resolved_name: Option<String>,
+    /// The installed version of the package the child is imported from
+    pub package_version: Option<String>,
+    /// Where the child is rendered in the parent's file
+    pub location: SourceLocation,
+    /// Spread attributes passed to the child
+    pub spreads: Vec<PropSpread>,
+}
+
+impl ComponentChild {
+    /// The name the child is registered under in the graph.
+    /// This is the resolved component if it differs from the rendered name.
+    pub fn component_name(&self) -> &str {
+        self.resolved_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Classifies where the child comes from, seen from the project it is rendered in.
+    /// Children that can't be resolved to a file or package are considered local.
+    pub fn source(&self, project_name: &str) -> ElementSource {
+        if self.package_version.is_some() {
+            return ElementSource::ThirdParty;
+        }
+
+        if self.origin_file_path.is_absolute() {
+            return match &self.project_name {
+                Some(name) if name != project_name => ElementSource::Workspace,
+                _ => ElementSource::Local,
+            };
+        }
+
+        // imports that could not be resolved fall back to their specifier
+        match self.origin_file_path.to_str() {
+            Some(specifier) if !specifier.is_empty() && !specifier.starts_with('.') => {
+                ElementSource::ThirdParty
+            }
+            _ => ElementSource::Local,
+        }
+    }
+
+    /// The place where the child is rendered, including its spread attributes
+    pub fn usage(&self) -> ComponentUsage {
+        let mut props: Vec<String> = self.props.keys().cloned().collect();
+        props.sort();
+
+        ComponentUsage {
+            location: self.location,
+            props,
+            spreads: self.spreads.clone(),
+            has_unknown_spread: self.spreads.iter().any(|spread| !spread.resolved),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentRoot {
     pub name: String,
-    pub props: HashMap<String, usize>,
+    pub props: BTreeMap<String, usize>,
+    pub declared_props: Vec<DeclaredProp>,
+    pub kind: ComponentKind,
+    /// Wrappers applied to the component, from outermost to innermost
+    pub wrappers: Vec<ComponentWrapper>,
+    /// Where the component is defined
+    pub location: SourceLocation,
     pub children: Vec<ComponentChild>,
+    /// Intrinsic elements like `div` rendered by the component, with usage counts
+    pub intrinsic_elements: BTreeMap<String, usize>,
+    /// Where each intrinsic element is rendered in the component's file
+    pub intrinsic_element_locations: BTreeMap<String, Vec<SourceLocation>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,15 +231,25 @@ pub struct Component {
     pub name: String,
     pub file_path: PathBuf,
     pub file_path_relative_to_root: Option<PathBuf>,
-    pub props: HashMap<String, usize>,
+    pub props: BTreeMap<String, usize>,
+    /// The props the component declares in its own signature
+    pub declared_props: Vec<DeclaredProp>,
+    pub kind: ComponentKind,
+    pub wrappers: Vec<ComponentWrapper>,
+    pub location: SourceLocation,
     pub children: Vec<ComponentChild>,
+    /// Intrinsic elements like `div` rendered by the component, with usage counts
+    pub intrinsic_elements: BTreeMap<String, usize>,
+    /// Where each intrinsic element is rendered in the component's file
+    #[serde(default)]
+    pub intrinsic_element_locations: BTreeMap<String, Vec<SourceLocation>>,
 }
 
 impl Component {
     pub fn new(
         name: String,
         file_path: PathBuf,
-        props: HashMap<String, usize>,
+        props: BTreeMap<String, usize>,
         children: Vec<ComponentChild>,
     ) -> Self {
         Self {
@@ -39,7 +257,13 @@ impl Component {
             file_path,
             file_path_relative_to_root: None,
             props,
+            declared_props: Vec::new(),
+            kind: ComponentKind::default(),
+            wrappers: Vec::new(),
+            location: SourceLocation::default(),
             children,
+            intrinsic_elements: BTreeMap::new(),
+            intrinsic_element_locations: BTreeMap::new(),
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::BTreeMap, path::PathBuf};
 
 use oxc_semantic::Semantic;
 
@@ -32,18 +32,28 @@ impl<'a> ReactAnalyzer<'a> {
     }
 
     pub fn analyze(&mut self) -> Vec<Component> {
-        let root_components =
-            extract_components(&self.semantic, &self.resolver, self.file_path.clone());
+        let root_components = extract_components(
+            self.semantic,
+            self.resolver,
+            self.package_resolver,
+            self.file_path.clone(),
+        );
         let mut components = Vec::new();
 
         for component in root_components {
-            let component = Component::new(
+            let mut analyzed = Component::new(
                 component.name.to_string(),
                 self.file_path.clone(),
-                HashMap::new(),
+                BTreeMap::new(),
                 component.children,
             );
-            components.push(component);
+            analyzed.declared_props = component.declared_props;
+            analyzed.kind = component.kind;
+            analyzed.wrappers = component.wrappers;
+            analyzed.location = component.location;
+            analyzed.intrinsic_elements = component.intrinsic_elements;
+            analyzed.intrinsic_element_locations = component.intrinsic_element_locations;
+            components.push(analyzed);
         }
 
         components
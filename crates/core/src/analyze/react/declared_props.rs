@@ -0,0 +1,487 @@
+use std::collections::HashSet;
+
+use oxc_ast::{
+    ast::{
+        BindingPattern, BindingPatternKind, Expression, FormalParameters, IdentifierReference,
+        TSSignature, TSType, TSTypeName,
+    },
+    AstKind,
+};
+use oxc_semantic::{AstNode, Semantic};
+use oxc_span::GetSpan;
+
+use crate::analyze::component::{ComponentWrapper, DeclaredProp};
+
+use super::root_components::{get_wrapper_kind, is_fc_type_name};
+
+/// Extracts the props a component declares in its own signature.
+///
+/// Props are collected from the type annotation of the first parameter, the type argument of a
+/// `FC<Props>`/`React.FC<Props>` annotation and destructured parameter names. Interfaces and type
+/// aliases referenced by these types are followed as long as they are declared in the same file.
+pub fn extract_declared_props(semantic: &Semantic, node: &AstNode) -> Vec<DeclaredProp> {
+    let mut extractor = DeclaredPropsExtractor {
+        semantic,
+        props: Vec::new(),
+        visited_types: HashSet::new(),
+    };
+
+    match node.kind() {
+        AstKind::Function(fn_decl) => {
+            extractor.visit_params(&fn_decl.params);
+        }
+        AstKind::VariableDeclaration(var_decl) => {
+            if let Some(declarator) = var_decl.declarations.first() {
+                if let Some(type_annotation) = &declarator.id.type_annotation {
+                    if let TSType::TSTypeReference(type_reference) =
+                        &type_annotation.type_annotation
+                    {
+                        if is_fc_type_name(&type_reference.type_name) {
+                            if let Some(props_type) = type_reference
+                                .type_parameters
+                                .as_ref()
+                                .and_then(|params| params.params.first())
+                            {
+                                extractor.visit_type(props_type);
+                            }
+                        }
+                    }
+                }
+
+                if let Some(init) = &declarator.init {
+                    extractor.visit_component_expression(init);
+                }
+            }
+        }
+        // class Button extends React.Component<ButtonProps>
+        AstKind::Class(class) => {
+            if let Some(props_type) = class
+                .super_type_parameters
+                .as_ref()
+                .and_then(|params| params.params.first())
+            {
+                extractor.visit_type(props_type);
+            }
+        }
+        _ => {}
+    }
+
+    extractor.props
+}
+
+struct DeclaredPropsExtractor<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    props: Vec<DeclaredProp>,
+    /// Names of the interfaces and type aliases that were already expanded, to avoid cycles
+    visited_types: HashSet<String>,
+}
+
+impl<'s, 'a> DeclaredPropsExtractor<'s, 'a> {
+    /// Adds a prop unless it is already known. Information from types takes precedence over
+    /// information from destructuring, so an existing entry only gets its missing type filled in.
+    fn add_prop(&mut self, prop: DeclaredProp) {
+        if let Some(existing) = self.props.iter_mut().find(|p| p.name == prop.name) {
+            if existing.type_annotation.is_none() {
+                existing.type_annotation = prop.type_annotation;
+            }
+            return;
+        }
+
+        self.props.push(prop);
+    }
+
+    /// Visits the expression a component is initialized with, following wrappers like
+    /// `memo<Props>(...)` and `forwardRef<Ref, Props>(...)` down to the function itself
+    fn visit_component_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::ArrowFunctionExpression(arrow_fn_expr) => {
+                self.visit_params(&arrow_fn_expr.params);
+            }
+            Expression::FunctionExpression(fn_expr) => {
+                self.visit_params(&fn_expr.params);
+            }
+            Expression::CallExpression(call_expr) => {
+                let Some(wrapper) = get_wrapper_kind(&call_expr.callee) else {
+                    return;
+                };
+
+                let props_type_index = match wrapper {
+                    ComponentWrapper::Memo => 0,
+                    ComponentWrapper::ForwardRef => 1,
+                    ComponentWrapper::Lazy => return,
+                };
+                if let Some(props_type) = call_expr
+                    .type_parameters
+                    .as_ref()
+                    .and_then(|params| params.params.get(props_type_index))
+                {
+                    self.visit_type(props_type);
+                }
+
+                if let Some(argument) = call_expr
+                    .arguments
+                    .first()
+                    .and_then(|arg| arg.as_expression())
+                {
+                    self.visit_component_expression(argument);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_params(&mut self, params: &FormalParameters) {
+        if let Some(first) = params.items.first() {
+            self.visit_binding_pattern(&first.pattern);
+        }
+    }
+
+    fn visit_binding_pattern(&mut self, pattern: &BindingPattern) {
+        if let Some(type_annotation) = &pattern.type_annotation {
+            self.visit_type(&type_annotation.type_annotation);
+        }
+
+        match &pattern.kind {
+            BindingPatternKind::ObjectPattern(object_pattern) => {
+                for property in &object_pattern.properties {
+                    let Some(name) = property.key.static_name() else {
+                        continue;
+                    };
+                    let has_default = matches!(
+                        property.value.kind,
+                        BindingPatternKind::AssignmentPattern(_)
+                    );
+
+                    self.add_prop(DeclaredProp {
+                        name: name.to_string(),
+                        optional: has_default,
+                        type_annotation: None,
+                    });
+                }
+            }
+            // `({ label } = {})`
+            BindingPatternKind::AssignmentPattern(assignment_pattern) => {
+                self.visit_binding_pattern(&assignment_pattern.left);
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_type(&mut self, ts_type: &TSType) {
+        match ts_type {
+            TSType::TSTypeLiteral(type_literal) => {
+                self.visit_signatures(&type_literal.members);
+            }
+            TSType::TSIntersectionType(intersection) => {
+                for ts_type in &intersection.types {
+                    self.visit_type(ts_type);
+                }
+            }
+            TSType::TSParenthesizedType(parenthesized) => {
+                self.visit_type(&parenthesized.type_annotation);
+            }
+            TSType::TSTypeReference(type_reference) => {
+                let resolved = match &type_reference.type_name {
+                    TSTypeName::IdentifierReference(identifier) => {
+                        self.visit_type_declaration(identifier)
+                    }
+                    TSTypeName::QualifiedName(_) => false,
+                };
+
+                // Wrappers like `PropsWithChildren<Props>` or `Readonly<Props>` are not declared
+                // locally, so we fall back to the props passed as the first type argument
+                if !resolved {
+                    if let Some(inner) = type_reference
+                        .type_parameters
+                        .as_ref()
+                        .and_then(|params| params.params.first())
+                    {
+                        self.visit_type(inner);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_signatures(&mut self, signatures: &[TSSignature]) {
+        let source_text = self.semantic.source_text();
+
+        for signature in signatures {
+            match signature {
+                TSSignature::TSPropertySignature(property) => {
+                    let Some(name) = property.key.static_name() else {
+                        continue;
+                    };
+
+                    self.add_prop(DeclaredProp {
+                        name: name.to_string(),
+                        optional: property.optional,
+                        type_annotation: property.type_annotation.as_ref().map(|annotation| {
+                            annotation
+                                .type_annotation
+                                .span()
+                                .source_text(source_text)
+                                .to_string()
+                        }),
+                    });
+                }
+                TSSignature::TSMethodSignature(method) => {
+                    let Some(name) = method.key.static_name() else {
+                        continue;
+                    };
+
+                    self.add_prop(DeclaredProp {
+                        name: name.to_string(),
+                        optional: method.optional,
+                        type_annotation: Some(method.span.source_text(source_text).to_string()),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Expands an interface or type alias declared in the current file.
+    /// Returns `false` if the identifier does not point to a local type declaration.
+    fn visit_type_declaration(&mut self, identifier: &IdentifierReference) -> bool {
+        let Some(symbol_id) = self
+            .semantic
+            .symbols()
+            .get_reference(identifier.reference_id())
+            .symbol_id()
+        else {
+            return false;
+        };
+
+        if !self.visited_types.insert(identifier.name.to_string()) {
+            return true;
+        }
+
+        let declaration = self.semantic.symbols().get_declaration(symbol_id);
+
+        match self.semantic.nodes().get_node(declaration).kind() {
+            AstKind::TSInterfaceDeclaration(interface) => {
+                self.visit_signatures(&interface.body.body);
+
+                for heritage in interface.extends.iter().flatten() {
+                    if let Expression::Identifier(parent) = &heritage.expression {
+                        self.visit_type_declaration(parent);
+                    }
+                }
+
+                true
+            }
+            AstKind::TSTypeAliasDeclaration(type_alias) => {
+                self.visit_type(&type_alias.type_annotation);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn};
+    use oxc_span::SourceType;
+
+    use super::*;
+
+    fn setup_semantic<'a>(allocator: &'a Allocator, content: &'a str) -> SemanticBuilderReturn<'a> {
+        let source_type = SourceType::default().with_typescript(true).with_jsx(true);
+
+        // Parse the source code
+        let parser_ret = Parser::new(allocator, content, source_type).parse();
+
+        let program = parser_ret.program;
+
+        // Build semantic analysis
+        SemanticBuilder::new().build(&program)
+    }
+
+    fn declared_props_of(content: &str, component_name: &str) -> Vec<DeclaredProp> {
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, content);
+        let semantic = semantic.semantic;
+
+        let node = semantic
+            .nodes()
+            .iter()
+            .find(|node| match node.kind() {
+                AstKind::Function(fn_decl) => fn_decl
+                    .id
+                    .as_ref()
+                    .is_some_and(|id| id.name == component_name),
+                AstKind::VariableDeclaration(var_decl) => var_decl
+                    .declarations
+                    .first()
+                    .and_then(|declarator| declarator.id.get_identifier())
+                    .is_some_and(|name| name == component_name),
+                _ => false,
+            })
+            .expect("Component not found");
+
+        extract_declared_props(&semantic, node)
+    }
+
+    #[test]
+    fn test_destructured_props_without_types() {
+        let props = declared_props_of(
+            r#"
+            function Button({ label, variant = 'primary' }) {
+                return <button>{label}</button>;
+            }
+            "#,
+            "Button",
+        );
+
+        assert_eq!(
+            props,
+            vec![
+                DeclaredProp {
+                    name: "label".to_string(),
+                    optional: false,
+                    type_annotation: None,
+                },
+                DeclaredProp {
+                    name: "variant".to_string(),
+                    optional: true,
+                    type_annotation: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_props_from_interface() {
+        let props = declared_props_of(
+            r#"
+            interface BaseProps {
+                id?: string;
+            }
+
+            interface ButtonProps extends BaseProps {
+                label: string;
+                onClick?: () => void;
+            }
+
+            function Button(props: ButtonProps) {
+                return <button>{props.label}</button>;
+            }
+            "#,
+            "Button",
+        );
+
+        let names: Vec<&str> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["label", "onClick", "id"]);
+        assert_eq!(props[0].type_annotation, Some("string".to_string()));
+        assert!(!props[0].optional);
+        assert_eq!(props[1].type_annotation, Some("() => void".to_string()));
+        assert!(props[1].optional);
+        assert!(props[2].optional);
+    }
+
+    #[test]
+    fn test_props_from_fc_generic() {
+        let props = declared_props_of(
+            r#"
+            type ButtonProps = { label: string } & { size?: 'sm' | 'lg' };
+
+            export const Button: React.FC<ButtonProps> = ({ label, size }) => {
+                return <button>{label}</button>;
+            };
+            "#,
+            "Button",
+        );
+
+        assert_eq!(
+            props,
+            vec![
+                DeclaredProp {
+                    name: "label".to_string(),
+                    optional: false,
+                    type_annotation: Some("string".to_string()),
+                },
+                DeclaredProp {
+                    name: "size".to_string(),
+                    optional: true,
+                    type_annotation: Some("'sm' | 'lg'".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_props_from_forward_ref_and_memo() {
+        let props = declared_props_of(
+            r#"
+            interface InputProps {
+                value: string;
+            }
+
+            export const Input = memo(forwardRef<HTMLInputElement, InputProps>(({ value, onChange }, ref) => {
+                return <input ref={ref} value={value} />;
+            }));
+            "#,
+            "Input",
+        );
+
+        let names: Vec<&str> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["value", "onChange"]);
+        assert_eq!(props[0].type_annotation, Some("string".to_string()));
+    }
+
+    #[test]
+    fn test_props_from_class_component() {
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(
+            &allocator,
+            r#"
+            type CounterProps = { initial?: number };
+
+            class Counter extends React.Component<CounterProps> {
+                render() {
+                    return <div>{this.props.initial}</div>;
+                }
+            }
+            "#,
+        );
+        let semantic = semantic.semantic;
+        let node = semantic
+            .nodes()
+            .iter()
+            .find(|node| matches!(node.kind(), AstKind::Class(_)))
+            .unwrap();
+
+        let props = extract_declared_props(&semantic, node);
+
+        assert_eq!(
+            props,
+            vec![DeclaredProp {
+                name: "initial".to_string(),
+                optional: true,
+                type_annotation: Some("number".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_props_from_inline_type_literal_and_wrapper() {
+        let props = declared_props_of(
+            r#"
+            const Card = ({ title, children }: PropsWithChildren<{ title: string }>) => {
+                return <div>{title}{children}</div>;
+            };
+            "#,
+            "Card",
+        );
+
+        let names: Vec<&str> = props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["title", "children"]);
+        assert_eq!(props[0].type_annotation, Some("string".to_string()));
+        assert_eq!(props[1].type_annotation, None);
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::{ExportDefaultDeclarationKind, JSXElementName, JSXOpeningElement, Statement},
+    Visit,
+};
+use oxc_semantic::{Semantic, SymbolFlags};
+use spinne_logger::Logger;
+
+use crate::{parse::parse_source, traverse::ProjectResolver};
+
+use super::{
+    find_component_root::recursive_find, find_import::find_import_for_symbol,
+    module_index::declared_names,
+};
+
+/// Components referenced by an entry point file
+#[derive(Debug, Default, PartialEq)]
+pub struct EntryComponents {
+    /// Components exported from the entry point. These form the public API of a library.
+    pub exported: Vec<(String, PathBuf)>,
+    /// Components rendered in the entry point, e.g. `<App />` in `createRoot(...).render(<App />)`
+    pub rendered: Vec<(String, PathBuf)>,
+}
+
+/// Finds the components exported and rendered by an entry point file.
+/// Every component is returned with its name and the path to the file where it is defined.
+pub fn find_entry_components(resolver: &ProjectResolver, file_path: &Path) -> EntryComponents {
+    let mut entry_components = EntryComponents::default();
+    let mut visited = HashSet::new();
+
+    collect_exports(
+        resolver,
+        file_path,
+        &mut visited,
+        &mut entry_components.exported,
+    );
+
+    if let Some(content) = read_file(file_path) {
+        let allocator = Allocator::default();
+        if let Ok((parser_ret, semantic_ret)) = parse_source(&allocator, file_path, &content) {
+            let mut visitor = RenderedComponentVisitor {
+                semantic: &semantic_ret.semantic,
+                resolver,
+                file_path,
+                components: Vec::new(),
+            };
+            visitor.visit_program(&parser_ret.program);
+            entry_components.rendered = visitor.components;
+        }
+    }
+
+    entry_components
+}
+
+/// Collects the components exported from a file, following `export * from` declarations.
+fn collect_exports(
+    resolver: &ProjectResolver,
+    file_path: &Path,
+    visited: &mut HashSet<PathBuf>,
+    exported: &mut Vec<(String, PathBuf)>,
+) {
+    if !visited.insert(file_path.to_path_buf()) {
+        return;
+    }
+
+    let Some(content) = read_file(file_path) else {
+        return;
+    };
+    let Some(directory) = file_path.parent().map(Path::to_path_buf) else {
+        return;
+    };
+
+    let allocator = Allocator::default();
+    let Ok((parser_ret, semantic_ret)) = parse_source(&allocator, file_path, &content) else {
+        Logger::error(&format!("Failed to parse file: {}", file_path.display()));
+        return;
+    };
+    let semantic = semantic_ret.semantic;
+
+    for statement in &parser_ret.program.body {
+        match statement {
+            Statement::ExportNamedDeclaration(export_decl) => {
+                if export_decl.export_kind.is_type() {
+                    continue;
+                }
+
+                if let Some(declaration) = &export_decl.declaration {
+                    for name in declared_names(declaration) {
+                        exported.push((name, file_path.to_path_buf()));
+                    }
+                }
+
+                for specifier in &export_decl.specifiers {
+                    if specifier.export_kind.is_type() {
+                        continue;
+                    }
+
+                    let local_name = specifier.local.name().to_string();
+                    let root = match &export_decl.source {
+                        // export { Button } from './Button';
+                        Some(source) => {
+                            recursive_find(resolver, &directory, &source.value, &local_name)
+                        }
+                        // export { Button };
+                        None => resolve_local(&semantic, resolver, file_path, &local_name),
+                    };
+
+                    if let Some(root) = root {
+                        exported.push(root);
+                    }
+                }
+            }
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                let root = match &export_decl.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(function) => function
+                        .id
+                        .as_ref()
+                        .map(|id| (id.name.to_string(), file_path.to_path_buf())),
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => class
+                        .id
+                        .as_ref()
+                        .map(|id| (id.name.to_string(), file_path.to_path_buf())),
+                    ExportDefaultDeclarationKind::Identifier(identifier) => {
+                        resolve_local(&semantic, resolver, file_path, &identifier.name)
+                    }
+                    _ => None,
+                };
+
+                if let Some(root) = root {
+                    exported.push(root);
+                }
+            }
+            // export * from './components';
+            Statement::ExportAllDeclaration(export_decl) => {
+                if export_decl.export_kind.is_type() || export_decl.exported.is_some() {
+                    continue;
+                }
+
+                match resolver.resolve(&directory, &export_decl.source.value) {
+                    Ok(resolution) => {
+                        let path = resolution.path();
+                        if !path.to_string_lossy().contains("node_modules") {
+                            collect_exports(resolver, path, visited, exported);
+                        }
+                    }
+                    Err(e) => Logger::warn(&format!(
+                        "Could not resolve export {} in {}: {}",
+                        export_decl.source.value,
+                        file_path.display(),
+                        e
+                    )),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Resolves a binding in the root scope of a file to the file where it is defined.
+/// Imported bindings are followed to their component root.
+fn resolve_local(
+    semantic: &Semantic,
+    resolver: &ProjectResolver,
+    file_path: &Path,
+    name: &str,
+) -> Option<(String, PathBuf)> {
+    let symbol_id = semantic.scopes().get_root_binding(name)?;
+
+    if SymbolFlags::is_import(&semantic.symbols().get_flags(symbol_id)) {
+        let import_node_id = find_import_for_symbol(semantic, symbol_id).ok()?;
+        let directory = file_path.parent()?.to_path_buf();
+
+        return super::find_component_root(semantic, resolver, &directory, import_node_id, name);
+    }
+
+    Some((name.to_string(), file_path.to_path_buf()))
+}
+
+fn read_file(file_path: &Path) -> Option<String> {
+    match std::fs::read_to_string(file_path) {
+        Ok(content) => Some(content),
+        Err(e) => {
+            Logger::warn(&format!(
+                "Failed to read entry point {}: {}",
+                file_path.display(),
+                e
+            ));
+            None
+        }
+    }
+}
+
+/// Collects the imported components that are rendered anywhere in a file
+struct RenderedComponentVisitor<'a, 'b> {
+    semantic: &'b Semantic<'a>,
+    resolver: &'b ProjectResolver,
+    file_path: &'b Path,
+    components: Vec<(String, PathBuf)>,
+}
+
+impl<'a> Visit<'a> for RenderedComponentVisitor<'a, '_> {
+    fn visit_jsx_opening_element(&mut self, jsx_opening_element: &JSXOpeningElement<'a>) {
+        if let JSXElementName::IdentifierReference(identifier) = &jsx_opening_element.name {
+            let symbol_id = self
+                .semantic
+                .symbols()
+                .get_reference(identifier.reference_id())
+                .symbol_id();
+
+            if let Some(symbol_id) = symbol_id {
+                if let Ok(import_node_id) = find_import_for_symbol(self.semantic, symbol_id) {
+                    let directory = self.file_path.parent().map(Path::to_path_buf);
+                    let root = directory.and_then(|directory| {
+                        super::find_component_root(
+                            self.semantic,
+                            self.resolver,
+                            &directory,
+                            import_node_id,
+                            &identifier.name,
+                        )
+                    });
+
+                    if let Some(root) = root {
+                        self.components.push(root);
+                    }
+                }
+            }
+        }
+
+        for attribute in &jsx_opening_element.attributes {
+            self.visit_jsx_attribute_item(attribute);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::test_utils::create_mock_project;
+
+    use super::*;
+
+    #[test]
+    fn test_find_entry_components() {
+        let temp_dir = create_mock_project(&vec![
+            (
+                "src/index.ts",
+                r#"
+                export { Button } from './Button';
+                export { Card as Panel } from './Card';
+                export * from './forms';
+                export type { ButtonProps } from './Button';
+                "#,
+            ),
+            (
+                "src/Button.tsx",
+                r#"
+                export type ButtonProps = {};
+                export const Button = () => <button />;
+                "#,
+            ),
+            (
+                "src/Card.tsx",
+                r#"
+                export function Card() {
+                    return <div />;
+                }
+                "#,
+            ),
+            (
+                "src/forms/index.ts",
+                r#"
+                import Input from './Input';
+                export { Input };
+                "#,
+            ),
+            (
+                "src/forms/Input.tsx",
+                r#"
+                export default function Input() {
+                    return <input />;
+                }
+                "#,
+            ),
+        ]);
+        let resolver = ProjectResolver::new(None);
+        let src = temp_dir.path().join("src");
+
+        let entry_components = find_entry_components(&resolver, &src.join("index.ts"));
+
+        assert_eq!(
+            entry_components.exported,
+            vec![
+                ("Button".to_string(), src.join("Button.tsx")),
+                ("Card".to_string(), src.join("Card.tsx")),
+                ("Input".to_string(), src.join("forms/Input.tsx")),
+            ]
+        );
+        assert!(entry_components.rendered.is_empty());
+    }
+
+    #[test]
+    fn test_find_rendered_components() {
+        let temp_dir = create_mock_project(&vec![
+            (
+                "src/main.tsx",
+                r#"
+                import { createRoot } from 'react-dom/client';
+                import { App } from './App';
+
+                createRoot(document.getElementById('root')).render(<App />);
+                "#,
+            ),
+            (
+                "src/App.tsx",
+                r#"
+                export const App = () => <div />;
+                "#,
+            ),
+        ]);
+        let resolver = ProjectResolver::new(None);
+        let src = temp_dir.path().join("src");
+
+        let entry_components = find_entry_components(&resolver, &src.join("main.tsx"));
+
+        assert!(entry_components.exported.is_empty());
+        assert_eq!(
+            entry_components.rendered,
+            vec![("App".to_string(), src.join("App.tsx"))]
+        );
+    }
+}
@@ -1,59 +1,28 @@
-use std::path::PathBuf;
-
-use oxc_allocator::Allocator;
-use oxc_ast::{
-    ast::{ExportNamedDeclaration, ModuleExportName},
-    AstKind, Visit,
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
 };
-use oxc_semantic::{NodeId, Semantic, SymbolFlags, SymbolId};
-use oxc_span::Atom;
+
+use oxc_ast::AstKind;
+use oxc_semantic::{NodeId, Semantic};
 use spinne_logger::Logger;
 
-use crate::{parse::parse_tsx, traverse::ProjectResolver, util::reduce_to_node_module_name};
+use crate::{
+    traverse::ProjectResolver,
+    util::{is_pascal_case, reduce_to_node_module_name},
+};
 
-use super::find_import::find_import_for_symbol;
+use super::module_index::{imported_name, Binding, Export, ModuleInfo};
 
-struct ComponentExportVisitor<'a> {
-    component_name: &'a str,
-    source_path: Option<Atom<'a>>,
-}
+const NODE_MODULES: &str = "node_modules/";
 
-impl<'a> Visit<'a> for ComponentExportVisitor<'a> {
-    fn visit_export_named_declaration(
-        &mut self,
-        export_named_declaration: &ExportNamedDeclaration<'a>,
-    ) {
-        let specifiers = &export_named_declaration.specifiers;
-        let source_path = if let Some(source) = &export_named_declaration.source {
-            Some(source.value.clone())
-        } else {
-            None
-        };
-
-        for specifier in specifiers {
-            match &specifier.local {
-                ModuleExportName::IdentifierReference(identifier_reference) => {
-                    if identifier_reference.name == self.component_name {
-                        self.source_path = source_path.clone();
-                    }
-                }
-                ModuleExportName::IdentifierName(identifier_name) => {
-                    if identifier_name.name == self.component_name {
-                        self.source_path = source_path.clone();
-                    }
-                }
-                ModuleExportName::StringLiteral(string_literal) => {
-                    if string_literal.value == self.component_name {
-                        self.source_path = source_path.clone();
-                    }
-                }
-            }
-        }
-    }
-}
+/// The name a namespace resolves to, e.g. `UI` in `export * as UI from './ui'`.
+/// The path of such a root is the module the namespace stands for.
+pub const NAMESPACE: &str = "*";
 
 /// Tries to find the root file where a component is defined.
 /// This is done by following the import declarations and looking for the component name in the export statements.
+/// Aliased and default imports are looked up by the name they are exported under.
 pub fn find_component_root(
     semantic: &Semantic,
     resolver: &ProjectResolver,
@@ -65,8 +34,11 @@ pub fn find_component_root(
 
     if let AstKind::ImportDeclaration(import_decl) = import_node.kind() {
         let specifier = import_decl.source.value.clone();
+        // the component is a property of a namespace import if no specifier binds its name
+        let component_name = imported_name(import_decl.specifiers.iter().flatten(), component_name)
+            .unwrap_or_else(|| component_name.to_string());
 
-        return recursive_find(resolver, file_path, &specifier, component_name);
+        return recursive_find(resolver, file_path, &specifier, &component_name);
     }
 
     None
@@ -75,12 +47,33 @@ pub fn find_component_root(
 /// Recursively finds the component root by following the import declarations and looking for the component name in the export statements.
 /// Returns the component name and the path to the file where the component is defined.
 /// If the component is not found, it returns the specifier as the component name and the path to the file where the component is defined.
-fn recursive_find(
+/// Modules are parsed once per resolver and the roots found for a module are remembered.
+pub(super) fn recursive_find(
     resolver: &ProjectResolver,
     file_path: &PathBuf,
     specifier: &str,
     component_name: &str,
 ) -> Option<(String, PathBuf)> {
+    let path = match resolve_module(resolver, file_path, specifier, component_name) {
+        Ok(path) => path,
+        Err(root) => return root,
+    };
+
+    resolver.get_root(&path, component_name, || {
+        find_export(resolver, &path, component_name, true, &mut HashSet::new())
+    })
+}
+
+/// Resolves a specifier to the module file it points to.
+/// Specifiers that can't be followed into a module return the component root directly:
+/// unresolvable specifiers fall back to the import path, packages in node_modules to the package name.
+/// Packages are recorded in the module index together with their installed version.
+fn resolve_module(
+    resolver: &ProjectResolver,
+    file_path: &PathBuf,
+    specifier: &str,
+    component_name: &str,
+) -> Result<PathBuf, Option<(String, PathBuf)>> {
     let resolved_path = resolver.resolve(file_path, specifier);
 
     if let Err(e) = resolved_path {
@@ -89,93 +82,257 @@ fn recursive_find(
             e
         ));
 
-        return Some((component_name.to_string(), specifier.to_string().into()));
+        return Err(Some((
+            component_name.to_string(),
+            specifier.to_string().into(),
+        )));
     }
 
     let resolved_path = resolved_path.unwrap();
 
-    // if the resolved path is a node_modules path, then we need to reduce the path to the node_modules name
-    if resolved_path
-        .path()
-        .to_str()
-        .unwrap()
-        .contains("node_modules")
-    {
-        let node_module_name = reduce_to_node_module_name(resolved_path.path().to_str().unwrap());
-        return Some((
-            node_module_name.to_string(),
-            node_module_name.to_string().into(),
-        ));
+    // components of installed packages are registered under the package name.
+    // Modules inside a package are followed, e.g. when its type declarations re-export other files.
+    if is_node_module(resolved_path.path()) && !is_node_module(file_path) {
+        return Err(Some(find_package_export(
+            resolver,
+            resolved_path.path(),
+            component_name,
+        )));
     }
 
-    if resolved_path.path().is_file() {
-        let content = std::fs::read_to_string(&resolved_path.path()).unwrap();
-        let directory = resolved_path.path().parent().unwrap().to_path_buf();
-        let allocator = Allocator::default();
-        let result = parse_tsx(&allocator, &resolved_path.path().to_path_buf(), &content);
-
-        if result.is_err() {
-            Logger::error(&format!(
-                "Failed to parse file: {}",
-                resolved_path.path().display()
-            ));
-            return None;
+    if !resolved_path.path().is_file() {
+        return Err(None);
+    }
+
+    Ok(resolved_path.path().to_path_buf())
+}
+
+/// Finds a component exported from an installed package.
+/// The component keeps the name it is imported by and gets the package name as its path.
+/// If the package entry is a type declaration file, it is searched to confirm the export exists
+/// and to find the name of the component behind a default export.
+fn find_package_export(
+    resolver: &ProjectResolver,
+    entry: &Path,
+    component_name: &str,
+) -> (String, PathBuf) {
+    let entry_path = entry.to_string_lossy();
+    let package_name = reduce_to_node_module_name(&entry_path);
+    let package_root = entry_path
+        .rfind(NODE_MODULES)
+        .map(|index| PathBuf::from(&entry_path[..index + NODE_MODULES.len() + package_name.len()]))
+        .unwrap_or_else(|| entry.to_path_buf());
+    let package = resolver.modules().add_package(&package_name, &package_root);
+    resolver.record_file(&package_root.join("package.json"));
+
+    let mut name = component_name.to_string();
+
+    if entry_path.ends_with(".d.ts") {
+        let root = resolver.get_root(entry, component_name, || {
+            find_export(resolver, entry, component_name, false, &mut HashSet::new())
+        });
+
+        match root {
+            Some((declared_name, _)) if component_name == "default" => {
+                if is_pascal_case(&declared_name) {
+                    name = declared_name;
+                }
+            }
+            Some(_) => {}
+            None => Logger::info(&format!(
+                "{} is not exported by the type declarations of {}",
+                component_name, package.name
+            )),
         }
+    }
 
-        let (parser_ret, semantic_ret) = result.unwrap();
-        let semantic = semantic_ret.semantic;
+    (name, PathBuf::from(package.name))
+}
 
-        let symbol_id = find_symbol_id(&semantic, component_name);
-        if let Some(symbol_id) = symbol_id {
-            let flags = semantic.symbols().get_flags(symbol_id);
+fn is_node_module(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == "node_modules")
+}
 
-            // if the symbol is a variable, then we have found the component root
-            if SymbolFlags::is_variable(&flags) {
-                return Some((
-                    component_name.to_string(),
-                    resolved_path.path().to_path_buf(),
-                ));
-            }
+/// Follows an import or re-export of a component into the module it comes from
+fn follow_import(
+    resolver: &ProjectResolver,
+    directory: &PathBuf,
+    specifier: &str,
+    component_name: &str,
+    visited: &mut HashSet<(PathBuf, String)>,
+) -> Option<(String, PathBuf)> {
+    match resolve_module(resolver, directory, specifier, component_name) {
+        Ok(path) => find_export(resolver, &path, component_name, true, visited),
+        Err(root) => root,
+    }
+}
 
-            // if the symbol is an import, then we need to find the component root of the imported file
-            if SymbolFlags::is_import(&flags) {
-                let import_node_id = find_import_for_symbol(&semantic, symbol_id);
-                let import_node = semantic.nodes().get_node(import_node_id.unwrap());
+/// Finds the root of a name exported from a module.
+/// Named exports take precedence over `export *` declarations. A name that is exported by more
+/// than one star export is ambiguous and not resolved, just like in ES modules.
+/// With `use_bindings` a name that is not exported is looked up in the bindings of the module.
+fn find_export(
+    resolver: &ProjectResolver,
+    path: &Path,
+    component_name: &str,
+    use_bindings: bool,
+    visited: &mut HashSet<(PathBuf, String)>,
+) -> Option<(String, PathBuf)> {
+    // modules re-exporting each other would otherwise recurse forever
+    if !visited.insert((path.to_path_buf(), component_name.to_string())) {
+        return None;
+    }
 
-                if let AstKind::ImportDeclaration(import_decl) = import_node.kind() {
-                    let specifier = import_decl.source.value.clone();
+    let module = resolver.modules().get(path)?;
+    let directory = path.parent()?.to_path_buf();
 
-                    return recursive_find(resolver, &directory, &specifier, component_name);
+    match module.exports.get(component_name) {
+        Some(Export::Local(local_name)) => {
+            return find_binding(resolver, path, &module, local_name, visited)
+        }
+        Some(Export::Reexport {
+            specifier,
+            imported,
+        }) => return follow_import(resolver, &directory, specifier, imported, visited),
+        Some(Export::Namespace(specifier)) => {
+            return follow_namespace(resolver, &directory, specifier, component_name)
+        }
+        // export default () => <div />;
+        Some(Export::Anonymous) => return Some((component_name.to_string(), path.to_path_buf())),
+        None => {}
+    }
+
+    // `export *` never re-exports the default export
+    if component_name != "default" {
+        let mut roots = Vec::new();
+
+        for specifier in &module.star_exports {
+            if let Ok(star_path) = resolve_module(resolver, &directory, specifier, component_name) {
+                if let Some(root) =
+                    find_export(resolver, &star_path, component_name, false, visited)
+                {
+                    if !roots.contains(&root) {
+                        roots.push(root);
+                    }
                 }
             }
         }
 
-        // else we try to find the component root by searching for the component name in the export statements
-        let mut visitor = ComponentExportVisitor {
-            component_name,
-            source_path: None,
-        };
-        visitor.visit_program(&parser_ret.program);
-
-        if let Some(source_path) = visitor.source_path {
-            return recursive_find(resolver, &directory, &source_path, component_name);
+        match roots.len() {
+            0 => {}
+            1 => return roots.pop(),
+            _ => {
+                Logger::warn(&format!(
+                    "{} is exported by more than one `export *` in {}",
+                    component_name,
+                    path.display()
+                ));
+                return None;
+            }
         }
     }
 
+    if use_bindings {
+        return find_binding(resolver, path, &module, component_name, visited);
+    }
+
     None
 }
 
-fn find_symbol_id(semantic: &Semantic, symbol_name: &str) -> Option<SymbolId> {
-    let symbol_id = semantic.symbols().symbol_ids();
-
-    for node in symbol_id {
-        let symbol = semantic.symbols().get_name(node);
-        if symbol == symbol_name {
-            return Some(node);
+/// Finds the root of a binding of a module, following it if it is imported
+fn find_binding(
+    resolver: &ProjectResolver,
+    path: &Path,
+    module: &ModuleInfo,
+    name: &str,
+    visited: &mut HashSet<(PathBuf, String)>,
+) -> Option<(String, PathBuf)> {
+    match module.bindings.get(name)? {
+        // if the symbol is a variable, then we have found the component root
+        Binding::Local => Some((name.to_string(), path.to_path_buf())),
+        // if the symbol is an import, then we need to find the component root of the imported file
+        Binding::Import {
+            specifier,
+            imported,
+        } if imported != NAMESPACE => follow_import(
+            resolver,
+            &path.parent()?.to_path_buf(),
+            specifier,
+            imported,
+            visited,
+        ),
+        Binding::Import { specifier, .. } => {
+            follow_namespace(resolver, &path.parent()?.to_path_buf(), specifier, name)
         }
+        _ => None,
     }
+}
 
-    None
+/// Follows a namespace into the module it stands for, e.g. `./ui` in `export * as UI from './ui'`.
+/// Namespaces of installed packages resolve to the package like any other import from it.
+fn follow_namespace(
+    resolver: &ProjectResolver,
+    directory: &PathBuf,
+    specifier: &str,
+    component_name: &str,
+) -> Option<(String, PathBuf)> {
+    match resolve_module(resolver, directory, specifier, component_name) {
+        Ok(path) => Some((NAMESPACE.to_string(), path)),
+        Err(root) => root,
+    }
+}
+
+/// Finds the component behind a member of a namespace, e.g. `TabPanel` in `<Panels.TabPanel />`
+/// where `Panels` is re-exported with `export * as Panels from './TabPanel'`.
+/// `module_path` is the module the namespace stands for.
+pub fn find_namespace_member(
+    resolver: &ProjectResolver,
+    module_path: &Path,
+    member_name: &str,
+) -> Option<(String, PathBuf)> {
+    resolver.get_root(module_path, member_name, || {
+        find_export(
+            resolver,
+            module_path,
+            member_name,
+            false,
+            &mut HashSet::new(),
+        )
+    })
+}
+
+/// Tries to find the component behind a static property of a component, e.g. `Panel` in `<Tabs.Panel />`.
+/// The file where the object component is defined is searched for `Tabs.Panel = TabPanel` or
+/// `const Tabs = Object.assign(TabsRoot, { Panel: TabPanel })`.
+/// Returns the name of the assigned component and the path to the file where it is defined.
+pub fn find_static_property_root(
+    resolver: &ProjectResolver,
+    file_path: &Path,
+    object_name: &str,
+    property_name: &str,
+) -> Option<(String, PathBuf)> {
+    if !file_path.is_file() {
+        return None;
+    }
+
+    let module = resolver.modules().get(file_path)?;
+    let identifier = module
+        .static_properties
+        .get(&ModuleInfo::static_property_key(object_name, property_name))?;
+
+    // the property is imported from another file, so we follow the import
+    if let Some(Binding::Import {
+        specifier,
+        imported,
+    }) = module.bindings.get(identifier)
+    {
+        let directory = file_path.parent()?.to_path_buf();
+
+        return recursive_find(resolver, &directory, specifier, imported);
+    }
+
+    Some((identifier.to_string(), file_path.to_path_buf()))
 }
 
 #[cfg(test)]
@@ -187,6 +344,8 @@ mod tests {
     use oxc_span::SourceType;
     use tempfile::TempDir;
 
+    use oxc_allocator::Allocator;
+
     use super::*;
 
     fn setup_semantic<'a>(allocator: &'a Allocator, content: &'a str) -> SemanticBuilderReturn<'a> {
@@ -410,4 +569,290 @@ mod tests {
         assert_eq!(result.0, "Input");
         assert_eq!(result.1, temp_dir.path().join("src/components/Input.tsx"));
     }
+
+    #[test]
+    fn test_find_star_and_aliased_exports() {
+        let resolver = ProjectResolver::new(None);
+
+        let project_files = vec![
+            (
+                "src/components/index.ts",
+                r#"
+                export * from './forms';
+                export * from './layout';
+                export { default as Button, Card as Panel } from './Button';
+                export { default } from './Button';
+                "#,
+            ),
+            (
+                "src/components/Button.tsx",
+                r#"
+                const PrimaryButton = () => <button />;
+                export default PrimaryButton;
+                export function Card() {
+                    return <div />;
+                }
+                "#,
+            ),
+            (
+                "src/components/forms/index.ts",
+                r#"
+                import Input from './Input';
+                export { Input as TextInput };
+                export { Stack } from '../layout/Stack';
+                export const Grid = () => null;
+                "#,
+            ),
+            (
+                "src/components/forms/Input.tsx",
+                "export default function Input() { return <input />; }",
+            ),
+            (
+                "src/components/layout/index.ts",
+                r#"
+                export * from './Stack';
+                export const Grid = () => null;
+                "#,
+            ),
+            (
+                "src/components/layout/Stack.tsx",
+                "export const Stack = () => <div />;",
+            ),
+        ];
+        let temp_dir = create_mock_project(&project_files);
+        let directory = temp_dir.path().join("src");
+        let find = |name: &str| recursive_find(&resolver, &directory, "./components", name);
+
+        assert_eq!(
+            find("Button"),
+            Some((
+                "PrimaryButton".to_string(),
+                temp_dir.path().join("src/components/Button.tsx")
+            ))
+        );
+        assert_eq!(
+            find("default"),
+            Some((
+                "PrimaryButton".to_string(),
+                temp_dir.path().join("src/components/Button.tsx")
+            ))
+        );
+        assert_eq!(
+            find("Panel"),
+            Some((
+                "Card".to_string(),
+                temp_dir.path().join("src/components/Button.tsx")
+            ))
+        );
+        assert_eq!(
+            find("TextInput"),
+            Some((
+                "Input".to_string(),
+                temp_dir.path().join("src/components/forms/Input.tsx")
+            ))
+        );
+
+        // both star exports lead to the same component
+        assert_eq!(
+            find("Stack"),
+            Some((
+                "Stack".to_string(),
+                temp_dir.path().join("src/components/layout/Stack.tsx")
+            ))
+        );
+
+        // different components with the same name are ambiguous
+        assert_eq!(find("Grid"), None);
+    }
+
+    #[test]
+    fn test_find_namespace_reexport_member() {
+        let resolver = ProjectResolver::new(None);
+
+        let project_files = vec![
+            (
+                "src/components/index.ts",
+                r#"
+                export * as Panels from './panels';
+                export * from './forms';
+                "#,
+            ),
+            (
+                "src/components/panels/index.ts",
+                r#"
+                export { TabPanel } from './TabPanel';
+                "#,
+            ),
+            (
+                "src/components/panels/TabPanel.tsx",
+                "export const TabPanel = () => <div />;",
+            ),
+            (
+                "src/components/forms/index.ts",
+                r#"
+                import * as Fields from './Input';
+                export { Fields };
+                "#,
+            ),
+            (
+                "src/components/forms/Input.tsx",
+                "export const Input = () => <input />;",
+            ),
+        ];
+        let temp_dir = create_mock_project(&project_files);
+        let directory = temp_dir.path().join("src");
+
+        let (name, module_path) =
+            recursive_find(&resolver, &directory, "./components", "Panels").unwrap();
+        assert_eq!(name, NAMESPACE);
+        assert_eq!(
+            module_path,
+            temp_dir.path().join("src/components/panels/index.ts")
+        );
+        assert_eq!(
+            find_namespace_member(&resolver, &module_path, "TabPanel"),
+            Some((
+                "TabPanel".to_string(),
+                temp_dir.path().join("src/components/panels/TabPanel.tsx")
+            ))
+        );
+        assert_eq!(
+            find_namespace_member(&resolver, &module_path, "Missing"),
+            None
+        );
+
+        // a namespace import that is exported again
+        let (name, module_path) =
+            recursive_find(&resolver, &directory, "./components", "Fields").unwrap();
+        assert_eq!(name, NAMESPACE);
+        assert_eq!(
+            find_namespace_member(&resolver, &module_path, "Input"),
+            Some((
+                "Input".to_string(),
+                temp_dir.path().join("src/components/forms/Input.tsx")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_find_anonymous_default_export() {
+        let resolver = ProjectResolver::new(None);
+
+        let project_files = vec![
+            ("src/Card.tsx", "export default () => <div />;"),
+            ("src/index.ts", "export { default as Card } from './Card';"),
+        ];
+        let temp_dir = create_mock_project(&project_files);
+        let directory = temp_dir.path().join("src");
+
+        assert_eq!(
+            recursive_find(&resolver, &directory, "./Card", "default"),
+            Some(("default".to_string(), temp_dir.path().join("src/Card.tsx")))
+        );
+        assert_eq!(
+            recursive_find(&resolver, &directory, "./index", "Card"),
+            Some(("default".to_string(), temp_dir.path().join("src/Card.tsx")))
+        );
+    }
+
+    #[test]
+    fn test_find_default_import() {
+        let allocator = Allocator::default();
+        let resolver = ProjectResolver::new(None);
+
+        let project_files = vec![
+            (
+                "src/components/App.tsx",
+                r#"
+                import Submit from './Button';
+
+                const App = () => <Submit />;
+            "#,
+            ),
+            (
+                "src/components/Button.tsx",
+                r#"
+                export default function Button() {
+                    return <button />;
+                }
+            "#,
+            ),
+        ];
+        let temp_dir = create_mock_project(&project_files);
+        let semantic = setup_semantic(&allocator, project_files[0].1);
+
+        let result = find_component_root(
+            &semantic.semantic,
+            &resolver,
+            &temp_dir.path().join("src/components"),
+            NodeId::new(2),
+            "Submit",
+        )
+        .unwrap();
+
+        assert_eq!(result.0, "Button");
+        assert_eq!(result.1, temp_dir.path().join("src/components/Button.tsx"));
+    }
+
+    #[test]
+    fn test_find_cyclic_star_exports() {
+        let resolver = ProjectResolver::new(None);
+
+        let project_files = vec![
+            ("src/a.ts", "export * from './b';"),
+            ("src/b.ts", "export * from './a';"),
+        ];
+        let temp_dir = create_mock_project(&project_files);
+
+        assert_eq!(
+            recursive_find(&resolver, &temp_dir.path().join("src"), "./a", "Button"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_package_export() {
+        let resolver = ProjectResolver::new(None);
+
+        let project_files = vec![
+            (
+                "node_modules/@acme/ui/package.json",
+                r#"{"name": "@acme/ui", "version": "2.1.0", "types": "dist/index.d.ts"}"#,
+            ),
+            (
+                "node_modules/@acme/ui/dist/index.d.ts",
+                r#"
+                import { FC } from 'react';
+                export * from './Button';
+                declare const Dialog: FC;
+                export { Dialog };
+                export default Dialog;
+                "#,
+            ),
+            (
+                "node_modules/@acme/ui/dist/Button.d.ts",
+                "export declare const Button: () => JSX.Element;",
+            ),
+        ];
+        let temp_dir = create_mock_project(&project_files);
+        let directory = temp_dir.path().join("src");
+        let find = |name: &str| recursive_find(&resolver, &directory, "@acme/ui", name);
+
+        assert_eq!(
+            find("Button"),
+            Some(("Button".to_string(), PathBuf::from("@acme/ui")))
+        );
+        assert_eq!(
+            find("default"),
+            Some(("Dialog".to_string(), PathBuf::from("@acme/ui")))
+        );
+        // components missing from the declarations keep their name
+        assert_eq!(
+            find("Tooltip"),
+            Some(("Tooltip".to_string(), PathBuf::from("@acme/ui")))
+        );
+
+        let package = resolver.modules().get_package("@acme/ui").unwrap();
+        assert_eq!(package.version, Some("2.1.0".to_string()));
+    }
 }
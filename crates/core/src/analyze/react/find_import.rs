@@ -8,7 +8,7 @@ use spinne_logger::Logger;
 pub fn find_import_for_symbol(semantic: &Semantic, symbol_id: SymbolId) -> Result<NodeId, String> {
     Logger::debug("Analyzing imports using symbol analysis", 2);
 
-    let import_node = recursive_find(&semantic, symbol_id);
+    let import_node = recursive_find(semantic, symbol_id);
 
     if let Some(import_node) = import_node {
         return Ok(import_node);
@@ -31,7 +31,9 @@ fn recursive_find(semantic: &Semantic, symbol_id: SymbolId) -> Option<NodeId> {
     // If we found an import, we're done!
     if matches!(
         declaration_node.kind(),
-        AstKind::ImportSpecifier(_) | AstKind::ImportDefaultSpecifier(_)
+        AstKind::ImportSpecifier(_)
+            | AstKind::ImportDefaultSpecifier(_)
+            | AstKind::ImportNamespaceSpecifier(_)
     ) {
         let parent_node = semantic.nodes().parent_node(declaration);
 
@@ -42,17 +44,15 @@ fn recursive_find(semantic: &Semantic, symbol_id: SymbolId) -> Option<NodeId> {
 
     // If not an import, check if it's a variable declaration and follow its reference
     if let AstKind::VariableDeclarator(var_decl) = declaration_node.kind() {
-        if let Some(init) = &var_decl.init {
-            if let Expression::Identifier(ident) = init {
-                // Recursively search for the new target and return its result
-                let symbol_id = semantic
-                    .symbols()
-                    .get_reference(ident.reference_id())
-                    .symbol_id();
-                if let Some(symbol_id) = symbol_id {
-                    if let Some(node) = recursive_find(semantic, symbol_id) {
-                        return Some(node);
-                    }
+        if let Some(Expression::Identifier(ident)) = &var_decl.init {
+            // Recursively search for the new target and return its result
+            let symbol_id = semantic
+                .symbols()
+                .get_reference(ident.reference_id())
+                .symbol_id();
+            if let Some(symbol_id) = symbol_id {
+                if let Some(node) = recursive_find(semantic, symbol_id) {
+                    return Some(node);
                 }
             }
         }
@@ -268,6 +268,25 @@ mod tests {
         assert_eq!(result.unwrap(), NodeId::new(2));
     }
 
+    #[test]
+    fn test_namespace_import() {
+        let content = r#"
+            import * as UI from './components';
+
+            const CustomButton = () => {
+                return <UI.Button />;
+            }
+        "#;
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, content);
+        let symbol_id = find_symbol_id(&semantic.semantic, "UI").expect("Symbol not found");
+
+        let result = find_import_for_symbol(&semantic.semantic, symbol_id);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), NodeId::new(2));
+    }
+
     #[test]
     fn test_import_component_with_dot_multiple() {
         let content = r#"
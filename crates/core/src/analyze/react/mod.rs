@@ -1,7 +1,12 @@
 pub mod analyzer;
+mod declared_props;
+mod entry_points;
 mod find_component_root;
 mod find_import;
+mod module_index;
 mod root_components;
 
-pub use find_component_root::find_component_root;
+pub use entry_points::find_entry_components;
+pub use find_component_root::{find_component_root, find_static_property_root};
+pub use module_index::{ComponentRoot, ModuleIndex};
 pub use root_components::extract_components;
@@ -0,0 +1,513 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::{
+        AssignmentTarget, Declaration, ExportDefaultDeclarationKind, Expression,
+        ImportDeclarationSpecifier, ObjectPropertyKind, Program, Statement,
+    },
+    AstKind,
+};
+use oxc_semantic::{Semantic, SymbolFlags};
+use spinne_logger::Logger;
+
+use crate::{package_json::PackageJson, parse::parse_source, traverse::SourceDependency};
+
+use super::find_import::find_import_for_symbol;
+
+/// How a name is bound in a module
+#[derive(Debug, Clone, PartialEq)]
+pub enum Binding {
+    /// A variable, function or class defined in the module itself
+    Local,
+    /// A binding imported from another module.
+    /// `imported` is the exported name in that module, `default` for default imports
+    /// and `*` for namespace imports.
+    Import { specifier: String, imported: String },
+    /// Any other symbol, e.g. a type or a parameter
+    Other,
+}
+
+/// What a module exports under a name
+#[derive(Debug, Clone, PartialEq)]
+pub enum Export {
+    /// A binding of the module itself, e.g. `export const Button`, `export { Button as Primary }`
+    /// or `export default Button`. Holds the local name.
+    Local(String),
+    /// A name re-exported from another module, e.g. `export { default as Button } from './Button'`
+    Reexport { specifier: String, imported: String },
+    /// A namespace re-export, e.g. `export * as UI from './ui'`. Holds the specifier.
+    Namespace(String),
+    /// An anonymous default export, e.g. `export default () => <div />`
+    Anonymous,
+}
+
+/// What a module defines, imports and re-exports, extracted from a single parse of the file
+#[derive(Debug, Default)]
+pub struct ModuleInfo {
+    /// How each name is bound. If a name is bound more than once, the first binding is kept.
+    pub bindings: HashMap<String, Binding>,
+    /// The exported names of the module, `default` for the default export
+    pub exports: HashMap<String, Export>,
+    /// Specifiers of the modules all names are re-exported from with `export * from`
+    pub star_exports: Vec<String>,
+    /// Identifiers assigned to static properties of components, keyed by `Object.property`,
+    /// e.g. `Tabs.Panel = TabPanel` or `Object.assign(Tabs, { Panel: TabPanel })`
+    pub static_properties: HashMap<String, String>,
+}
+
+impl ModuleInfo {
+    /// Parses a file and collects its bindings, re-exports and static properties
+    fn read(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let allocator = Allocator::default();
+
+        let Ok((parser_ret, semantic_ret)) = parse_source(&allocator, path, &content) else {
+            Logger::error(&format!("Failed to parse file: {}", path.display()));
+            return None;
+        };
+        let semantic = semantic_ret.semantic;
+
+        // semantic analysis skips type declaration files, so their bindings are read from the AST
+        let bindings = if semantic.source_type().is_typescript_definition() {
+            collect_declared_bindings(&parser_ret.program)
+        } else {
+            collect_bindings(&semantic)
+        };
+
+        let mut module = Self {
+            bindings,
+            static_properties: collect_static_properties(&semantic),
+            ..Self::default()
+        };
+        module.collect_exports(&parser_ret.program);
+
+        Some(module)
+    }
+
+    /// Collects the exports of the module. Type-only exports are skipped.
+    fn collect_exports(&mut self, program: &Program) {
+        for statement in &program.body {
+            match statement {
+                Statement::ExportNamedDeclaration(export_decl) => {
+                    if export_decl.export_kind.is_type() {
+                        continue;
+                    }
+
+                    // export const Button = () => <button />;
+                    if let Some(declaration) = &export_decl.declaration {
+                        for name in declared_names(declaration) {
+                            self.exports.insert(name.clone(), Export::Local(name));
+                        }
+                    }
+
+                    for specifier in &export_decl.specifiers {
+                        if specifier.export_kind.is_type() {
+                            continue;
+                        }
+
+                        let local = specifier.local.name().to_string();
+                        let export = match &export_decl.source {
+                            // export { Button as Primary } from './Button';
+                            Some(source) => Export::Reexport {
+                                specifier: source.value.to_string(),
+                                imported: local,
+                            },
+                            // export { Button as Primary };
+                            None => Export::Local(local),
+                        };
+
+                        self.exports
+                            .insert(specifier.exported.name().to_string(), export);
+                    }
+                }
+                Statement::ExportDefaultDeclaration(export_decl) => {
+                    let export = match &export_decl.declaration {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(function) => function
+                            .id
+                            .as_ref()
+                            .map_or(Export::Anonymous, |id| Export::Local(id.name.to_string())),
+                        ExportDefaultDeclarationKind::ClassDeclaration(class) => class
+                            .id
+                            .as_ref()
+                            .map_or(Export::Anonymous, |id| Export::Local(id.name.to_string())),
+                        ExportDefaultDeclarationKind::TSInterfaceDeclaration(_) => continue,
+                        ExportDefaultDeclarationKind::Identifier(identifier) => {
+                            Export::Local(identifier.name.to_string())
+                        }
+                        _ => Export::Anonymous,
+                    };
+
+                    self.exports.insert("default".to_string(), export);
+                }
+                Statement::ExportAllDeclaration(export_decl) => {
+                    if export_decl.export_kind.is_type() {
+                        continue;
+                    }
+
+                    let source = export_decl.source.value.to_string();
+                    match &export_decl.exported {
+                        // export * as UI from './ui';
+                        Some(exported) => {
+                            self.exports
+                                .insert(exported.name().to_string(), Export::Namespace(source));
+                        }
+                        // export * from './ui';
+                        None => self.star_exports.push(source),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Gets the key a static property is stored under
+    pub fn static_property_key(object_name: &str, property_name: &str) -> String {
+        format!("{}.{}", object_name, property_name)
+    }
+}
+
+/// A package installed in node_modules
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstalledPackage {
+    pub name: String,
+    /// The version from the package's `package.json`
+    pub version: Option<String>,
+}
+
+/// The name and file a component is defined in
+pub type ComponentRoot = (String, PathBuf);
+
+/// A component root and the dependencies recorded while finding it, if they were recorded
+type RootEntry = (Option<ComponentRoot>, Option<Arc<[SourceDependency]>>);
+
+/// Parses every module at most once per run and shares the result between all lookups.
+/// Component roots that were found by following imports and re-exports are remembered as well.
+#[derive(Debug, Default)]
+pub struct ModuleIndex {
+    modules: RwLock<HashMap<PathBuf, Option<Arc<ModuleInfo>>>>,
+    roots: RwLock<HashMap<(PathBuf, String), RootEntry>>,
+    /// Installed packages by name. If a package is installed more than once, the first one found is kept.
+    packages: RwLock<HashMap<String, InstalledPackage>>,
+}
+
+impl ModuleIndex {
+    /// Gets the module of a file, parsing it if it wasn't indexed yet
+    pub fn get(&self, path: &Path) -> Option<Arc<ModuleInfo>> {
+        if let Some(module) = self.modules.read().unwrap().get(path) {
+            return module.clone();
+        }
+
+        let module = ModuleInfo::read(path).map(Arc::new);
+        self.modules
+            .write()
+            .unwrap()
+            .insert(path.to_path_buf(), module.clone());
+
+        module
+    }
+
+    /// Gets the root of a component exported from a module if it was found before
+    pub(crate) fn get_root(&self, path: &Path, component_name: &str) -> Option<RootEntry> {
+        self.roots
+            .read()
+            .unwrap()
+            .get(&(path.to_path_buf(), component_name.to_string()))
+            .cloned()
+    }
+
+    /// Remembers the root of a component exported from a module
+    pub(crate) fn insert_root(
+        &self,
+        path: &Path,
+        component_name: &str,
+        root: Option<ComponentRoot>,
+        dependencies: Option<Arc<[SourceDependency]>>,
+    ) {
+        self.roots.write().unwrap().insert(
+            (path.to_path_buf(), component_name.to_string()),
+            (root, dependencies),
+        );
+    }
+
+    /// Records a package that components are imported from and reads its version
+    pub fn add_package(&self, name: &str, package_root: &Path) -> InstalledPackage {
+        if let Some(package) = self.packages.read().unwrap().get(name) {
+            return package.clone();
+        }
+
+        let package_json_path = package_root.join("package.json");
+        let version = package_json_path
+            .is_file()
+            .then(|| PackageJson::read(&package_json_path, false))
+            .flatten()
+            .and_then(|package_json| package_json.version);
+        let package = InstalledPackage {
+            name: name.to_string(),
+            version,
+        };
+
+        self.packages
+            .write()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert(package)
+            .clone()
+    }
+
+    /// Gets a package that components were imported from
+    pub fn get_package(&self, name: &str) -> Option<InstalledPackage> {
+        self.packages.read().unwrap().get(name).cloned()
+    }
+}
+
+/// Collects how the names of a module are bound
+fn collect_bindings(semantic: &Semantic) -> HashMap<String, Binding> {
+    let symbols = semantic.symbols();
+    let mut bindings = HashMap::new();
+
+    for symbol_id in symbols.symbol_ids() {
+        let name = symbols.get_name(symbol_id);
+        if bindings.contains_key(name) {
+            continue;
+        }
+
+        let flags = symbols.get_flags(symbol_id);
+        let binding = if flags.intersects(SymbolFlags::Variable | SymbolFlags::Class) {
+            Binding::Local
+        } else if SymbolFlags::is_import(&flags) {
+            find_import_for_symbol(semantic, symbol_id)
+                .ok()
+                .and_then(|node_id| match semantic.nodes().get_node(node_id).kind() {
+                    AstKind::ImportDeclaration(import_decl) => Some(Binding::Import {
+                        specifier: import_decl.source.value.to_string(),
+                        imported: imported_name(import_decl.specifiers.iter().flatten(), name)?,
+                    }),
+                    _ => None,
+                })
+                .unwrap_or(Binding::Other)
+        } else {
+            Binding::Other
+        };
+
+        bindings.insert(name.to_string(), binding);
+    }
+
+    bindings
+}
+
+/// Collects the bindings declared by the top level statements of a module
+fn collect_declared_bindings(program: &Program) -> HashMap<String, Binding> {
+    let mut bindings = HashMap::new();
+
+    for statement in &program.body {
+        match statement {
+            Statement::ImportDeclaration(import_decl) => {
+                for specifier in import_decl.specifiers.iter().flatten() {
+                    let name = specifier.name();
+                    let imported = imported_name([specifier], &name).unwrap_or_default();
+
+                    bindings
+                        .entry(name.to_string())
+                        .or_insert_with(|| Binding::Import {
+                            specifier: import_decl.source.value.to_string(),
+                            imported,
+                        });
+                }
+            }
+            Statement::ExportNamedDeclaration(export_decl) => {
+                for name in export_decl.declaration.iter().flat_map(declared_names) {
+                    bindings.entry(name).or_insert(Binding::Local);
+                }
+            }
+            _ => {
+                for name in statement
+                    .as_declaration()
+                    .into_iter()
+                    .flat_map(declared_names)
+                {
+                    bindings.entry(name).or_insert(Binding::Local);
+                }
+            }
+        }
+    }
+
+    bindings
+}
+
+/// Collects the identifiers assigned to static properties
+fn collect_static_properties(semantic: &Semantic) -> HashMap<String, String> {
+    let mut static_properties = HashMap::new();
+
+    for node in semantic.nodes().iter() {
+        match node.kind() {
+            // Tabs.Panel = TabPanel;
+            AstKind::AssignmentExpression(assignment) => {
+                let AssignmentTarget::StaticMemberExpression(member_expr) = &assignment.left else {
+                    continue;
+                };
+                let (Expression::Identifier(object), Expression::Identifier(identifier)) =
+                    (&member_expr.object, &assignment.right)
+                else {
+                    continue;
+                };
+
+                static_properties
+                    .entry(ModuleInfo::static_property_key(
+                        &object.name,
+                        &member_expr.property.name,
+                    ))
+                    .or_insert_with(|| identifier.name.to_string());
+            }
+            // const Tabs = Object.assign(TabsRoot, { Panel: TabPanel });
+            AstKind::VariableDeclarator(declarator) => {
+                let Some(object_name) = declarator.id.get_identifier() else {
+                    continue;
+                };
+                let Some(Expression::CallExpression(call_expr)) = &declarator.init else {
+                    continue;
+                };
+                let Expression::StaticMemberExpression(callee) = &call_expr.callee else {
+                    continue;
+                };
+                if callee.property.name != "assign"
+                    || !matches!(&callee.object, Expression::Identifier(object) if object.name == "Object")
+                {
+                    continue;
+                }
+
+                let properties = call_expr
+                    .arguments
+                    .iter()
+                    .skip(1)
+                    .filter_map(|argument| match argument.as_expression() {
+                        Some(Expression::ObjectExpression(object)) => Some(object),
+                        _ => None,
+                    })
+                    .flat_map(|object| object.properties.iter());
+
+                for property in properties {
+                    let ObjectPropertyKind::ObjectProperty(property) = property else {
+                        continue;
+                    };
+                    let (Some(property_name), Expression::Identifier(identifier)) =
+                        (property.key.static_name(), &property.value)
+                    else {
+                        continue;
+                    };
+
+                    static_properties
+                        .entry(ModuleInfo::static_property_key(
+                            &object_name,
+                            &property_name,
+                        ))
+                        .or_insert_with(|| identifier.name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    static_properties
+}
+
+/// Gets the name a local binding is exported under in the module it is imported from.
+/// Returns `default` for default imports and `*` for namespace imports.
+pub(super) fn imported_name<'s, 'a: 's>(
+    specifiers: impl IntoIterator<Item = &'s ImportDeclarationSpecifier<'a>>,
+    local_name: &str,
+) -> Option<String> {
+    specifiers
+        .into_iter()
+        .find_map(|specifier| match specifier {
+            ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                (specifier.local.name == local_name).then(|| specifier.imported.name().to_string())
+            }
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                (specifier.local.name == local_name).then(|| "default".to_string())
+            }
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                (specifier.local.name == local_name).then(|| "*".to_string())
+            }
+        })
+}
+
+/// Returns the names of the bindings created by an exported declaration
+pub(super) fn declared_names(declaration: &Declaration) -> Vec<String> {
+    match declaration {
+        Declaration::VariableDeclaration(variable_decl) => variable_decl
+            .declarations
+            .iter()
+            .filter_map(|declarator| declarator.id.get_identifier())
+            .map(|name| name.to_string())
+            .collect(),
+        Declaration::FunctionDeclaration(function) => {
+            function.id.iter().map(|id| id.name.to_string()).collect()
+        }
+        Declaration::ClassDeclaration(class) => {
+            class.id.iter().map(|id| id.name.to_string()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::test_utils::create_mock_project;
+
+    use super::*;
+
+    #[test]
+    fn test_module_info() {
+        let temp_dir = create_mock_project(&vec![(
+            "src/index.tsx",
+            r#"
+            import { TabPanel } from './TabPanel';
+            export { Button, Icon } from './Button';
+
+            type TabsProps = {};
+            class Legacy extends React.Component {}
+            const TabsRoot = (props: TabsProps) => <div />;
+            export const Tabs = Object.assign(TabsRoot, { Panel: TabPanel });
+            Tabs.List = TabsRoot;
+            "#,
+        )]);
+        let index = ModuleIndex::default();
+        let path = temp_dir.path().join("src/index.tsx");
+
+        let module = index.get(&path).unwrap();
+
+        assert_eq!(
+            module.bindings.get("TabPanel"),
+            Some(&Binding::Import {
+                specifier: "./TabPanel".to_string(),
+                imported: "TabPanel".to_string()
+            })
+        );
+        assert_eq!(module.bindings.get("TabsRoot"), Some(&Binding::Local));
+        assert_eq!(module.bindings.get("Legacy"), Some(&Binding::Local));
+        assert_eq!(module.bindings.get("TabsProps"), Some(&Binding::Other));
+        assert_eq!(
+            module.exports.get("Icon"),
+            Some(&Export::Reexport {
+                specifier: "./Button".to_string(),
+                imported: "Icon".to_string()
+            })
+        );
+        assert_eq!(
+            module.static_properties.get("Tabs.Panel"),
+            Some(&"TabPanel".to_string())
+        );
+        assert_eq!(
+            module.static_properties.get("Tabs.List"),
+            Some(&"TabsRoot".to_string())
+        );
+
+        // the module is parsed once and shared afterwards
+        assert!(Arc::ptr_eq(&module, &index.get(&path).unwrap()));
+    }
+}
@@ -1,61 +1,136 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::BTreeMap, path::PathBuf};
 
 use oxc_ast::{
     ast::{
-        Expression, FunctionBody, JSXAttributeItem, JSXAttributeName, JSXElementName,
-        JSXOpeningElement, Statement, TSType, TSTypeAnnotation, TSTypeName,
+        ArrowFunctionExpression, BindingPatternKind, Class, ClassElement, Expression, Function,
+        FunctionBody, IdentifierReference, JSXAttributeItem, JSXAttributeName, JSXAttributeValue,
+        JSXElementName, JSXMemberExpression, JSXMemberExpressionObject, JSXOpeningElement,
+        ObjectExpression, ObjectPropertyKind, ReturnStatement, TSType, TSTypeAnnotation,
+        TSTypeName,
     },
     AstKind, Visit,
 };
-use oxc_semantic::{AstNode, Semantic};
+use oxc_semantic::{AstNode, ScopeFlags, Semantic};
+use oxc_span::{GetSpan, Span};
 use spinne_logger::Logger;
 
 use crate::{
-    analyze::component::{ComponentChild, ComponentRoot},
+    analyze::component::{
+        ComponentChild, ComponentKind, ComponentRoot, ComponentWrapper, PropSpread, PropValueKind,
+        PropValueStats, SourceLocation,
+    },
     traverse::{PackageResolver, ProjectResolver},
     util,
 };
 
-use super::{find_component_root, find_import::find_import_for_symbol};
+use super::{
+    declared_props::extract_declared_props,
+    find_component_root,
+    find_component_root::{find_namespace_member, NAMESPACE},
+    find_import::find_import_for_symbol,
+    find_static_property_root,
+};
 
 /// Check if the identifier is in pascal case
 fn has_correct_case(identifier: &str) -> bool {
-    util::is_pascal_case(&identifier.to_string())
+    util::is_pascal_case(identifier)
 }
 
-/// Check if the function body has a return statement that matches the react return type
+/// Check if the expression is something react can render.
 /// This could be a JSXElement, JSXFragment, NullLiteral etc.
-fn has_react_return(body: &FunctionBody) -> bool {
-    body.statements.iter().any(|node| match node {
-        Statement::ReturnStatement(return_statement) => {
-            if let Some(argument) = &return_statement.argument {
-                match argument {
-                    Expression::JSXElement(_) => {
-                        return true;
-                    }
-                    Expression::JSXFragment(_) => {
-                        return true;
-                    }
-                    Expression::NullLiteral(_) => {
-                        return true;
-                    }
-                    Expression::BooleanLiteral(_) => {
-                        return true;
-                    }
-                    Expression::StringLiteral(_) => {
-                        return true;
-                    }
-                    Expression::NumericLiteral(_) => {
-                        return true;
-                    }
-                    _ => {}
-                }
+/// Conditional, logical and sequence expressions are evaluated for every value they can produce.
+fn is_react_expression(expression: &Expression) -> bool {
+    match expression {
+        Expression::JSXElement(_)
+        | Expression::JSXFragment(_)
+        | Expression::NullLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::NumericLiteral(_) => true,
+        // (<div />)
+        Expression::ParenthesizedExpression(parenthesized) => {
+            is_react_expression(&parenthesized.expression)
+        }
+        // cond ? <A /> : <B />
+        Expression::ConditionalExpression(conditional) => {
+            is_react_expression(&conditional.consequent)
+                || is_react_expression(&conditional.alternate)
+        }
+        // loading && <Spinner />
+        Expression::LogicalExpression(logical) => {
+            is_react_expression(&logical.left) || is_react_expression(&logical.right)
+        }
+        // (track(), <div />)
+        Expression::SequenceExpression(sequence) => sequence
+            .expressions
+            .last()
+            .is_some_and(|expression| is_react_expression(expression)),
+        _ => false,
+    }
+}
+
+/// Looks for return statements that return something react can render.
+/// Return statements of nested functions and classes are skipped, as they don't belong to the component.
+#[derive(Default)]
+struct ReactReturnVisitor {
+    has_react_return: bool,
+}
+
+impl<'a> Visit<'a> for ReactReturnVisitor {
+    fn visit_return_statement(&mut self, return_statement: &ReturnStatement<'a>) {
+        if let Some(argument) = &return_statement.argument {
+            if is_react_expression(argument) {
+                self.has_react_return = true;
             }
+        }
+    }
+
+    fn visit_function(&mut self, _function: &Function<'a>, _flags: ScopeFlags) {}
 
-            return false;
+    fn visit_arrow_function_expression(&mut self, _arrow_fn_expr: &ArrowFunctionExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &Class<'a>) {}
+}
+
+/// Check if any return path of the function body matches the react return type,
+/// including returns nested in `if`, `switch`, `try` and loop statements
+fn has_react_return(body: &FunctionBody) -> bool {
+    let mut visitor = ReactReturnVisitor::default();
+    visitor.visit_function_body(body);
+
+    visitor.has_react_return
+}
+
+/// Check if the arrow function returns a react type, either from its concise body or its block body
+fn has_react_arrow_return(arrow_fn_expr: &ArrowFunctionExpression) -> bool {
+    match arrow_fn_expr.get_expression() {
+        Some(expression) => is_react_expression(expression),
+        None => has_react_return(&arrow_fn_expr.body),
+    }
+}
+
+/// Check if the type name is a react function component type e.g. React.FC
+pub(super) fn is_fc_type_name(type_name: &TSTypeName) -> bool {
+    // React.FC
+    if let TSTypeName::QualifiedName(qualified_name) = type_name {
+        let left = &qualified_name.left;
+        let right = &qualified_name.right;
+
+        if let TSTypeName::IdentifierReference(identifier_reference) = left {
+            let ident_ref = &identifier_reference.name;
+
+            return ident_ref == "React" && right.name == "FC";
         }
-        _ => false,
-    })
+    }
+
+    // FC
+    if let TSTypeName::IdentifierReference(identifier_reference) = type_name {
+        let ident_ref = &identifier_reference.name;
+
+        return ident_ref == "FC";
+    }
+
+    false
 }
 
 /// Check if the type annotation is a react type annotation e.g. React.FC
@@ -64,30 +139,83 @@ fn has_react_type_annotation(
 ) -> bool {
     if let Some(type_annotation) = type_annotation {
         if let TSType::TSTypeReference(type_reference) = &type_annotation.type_annotation {
-            let type_name = &type_reference.type_name;
-
-            // React.FC
-            if let TSTypeName::QualifiedName(qualified_name) = type_name {
-                let left = &qualified_name.left;
-                let right = &qualified_name.right;
+            return is_fc_type_name(&type_reference.type_name);
+        }
+    }
 
-                if let TSTypeName::IdentifierReference(identifier_reference) = left {
-                    let ident_ref = &identifier_reference.name;
+    false
+}
 
-                    return ident_ref == "React" && right.name == "FC";
-                }
+/// Get the wrapper kind if the callee is a react wrapper like `memo`, `React.forwardRef` or `lazy`
+pub(super) fn get_wrapper_kind(callee: &Expression) -> Option<ComponentWrapper> {
+    let name = match callee {
+        Expression::Identifier(identifier) => identifier.name.as_str(),
+        Expression::StaticMemberExpression(member_expr) => match &member_expr.object {
+            Expression::Identifier(object) if object.name == "React" => {
+                member_expr.property.name.as_str()
             }
+            _ => return None,
+        },
+        _ => return None,
+    };
 
-            // FC
-            if let TSTypeName::IdentifierReference(identifier_reference) = type_name {
-                let ident_ref = &identifier_reference.name;
+    match name {
+        "memo" => Some(ComponentWrapper::Memo),
+        "forwardRef" => Some(ComponentWrapper::ForwardRef),
+        "lazy" => Some(ComponentWrapper::Lazy),
+        _ => None,
+    }
+}
 
-                return ident_ref == "FC";
-            }
+/// Unwraps wrapper calls like `memo(forwardRef(...))`.
+/// Returns the innermost wrapped expression and the wrappers from outermost to innermost.
+fn unwrap_component_wrappers<'b, 'a>(
+    expression: &'b Expression<'a>,
+) -> (&'b Expression<'a>, Vec<ComponentWrapper>) {
+    let mut current = expression;
+    let mut wrappers = Vec::new();
+
+    while let Expression::CallExpression(call_expr) = current {
+        let Some(wrapper) = get_wrapper_kind(&call_expr.callee) else {
+            break;
+        };
+        let Some(argument) = call_expr
+            .arguments
+            .first()
+            .and_then(|arg| arg.as_expression())
+        else {
+            break;
+        };
+
+        wrappers.push(wrapper);
+        current = argument;
+    }
+
+    (current, wrappers)
+}
+
+/// Check if the class extends `React.Component`, `React.PureComponent` or their named imports
+fn extends_react_component(class: &Class) -> bool {
+    let is_component_name = |name: &str| name == "Component" || name == "PureComponent";
+
+    match &class.super_class {
+        Some(Expression::Identifier(identifier)) => is_component_name(&identifier.name),
+        Some(Expression::StaticMemberExpression(member_expr)) => {
+            matches!(&member_expr.object, Expression::Identifier(object) if object.name == "React")
+                && is_component_name(&member_expr.property.name)
         }
+        _ => false,
     }
+}
 
-    false
+/// Get the body of the `render` method of a class component
+fn get_render_body<'a>(class: &'a Class<'a>) -> Option<&'a FunctionBody<'a>> {
+    class.body.body.iter().find_map(|element| match element {
+        ClassElement::MethodDefinition(method) if method.key.is_specific_static_name("render") => {
+            method.value.body.as_deref()
+        }
+        _ => None,
+    })
 }
 
 /// Check if the node is a react component
@@ -99,7 +227,7 @@ fn is_react_component(node: &AstNode) -> bool {
             Logger::debug(&format!("Analyzing function declaration: {:?}", name), 3);
 
             if let Some(name) = name {
-                if !has_correct_case(&name.name.to_string()) {
+                if !has_correct_case(name.name.as_ref()) {
                     return false;
                 }
 
@@ -110,7 +238,16 @@ fn is_react_component(node: &AstNode) -> bool {
                 }
             }
 
-            return false;
+            false
+        }
+        AstKind::Class(class) => {
+            let Some(name) = &class.id else {
+                return false;
+            };
+
+            Logger::debug(&format!("Analyzing class declaration: {}", name.name), 3);
+
+            has_correct_case(name.name.as_ref()) && extends_react_component(class)
         }
         AstKind::VariableDeclaration(var_decl) => {
             let name = var_decl.declarations.first();
@@ -130,7 +267,7 @@ fn is_react_component(node: &AstNode) -> bool {
                     3,
                 );
 
-                if !has_correct_case(&identifier.to_string()) {
+                if !has_correct_case(identifier.as_ref()) {
                     return false;
                 }
 
@@ -139,16 +276,29 @@ fn is_react_component(node: &AstNode) -> bool {
                 }
 
                 if let Some(init) = &name.init {
-                    if let Expression::ArrowFunctionExpression(arrow_fn_expr) = init {
-                        let body = &arrow_fn_expr.body;
-                        if has_react_return(body) {
-                            return true;
+                    let (inner, wrappers) = unwrap_component_wrappers(init);
+
+                    // memo, forwardRef and lazy always produce a component
+                    if !wrappers.is_empty() {
+                        return true;
+                    }
+
+                    match inner {
+                        Expression::ArrowFunctionExpression(arrow_fn_expr) => {
+                            return has_react_arrow_return(arrow_fn_expr);
+                        }
+                        Expression::FunctionExpression(fn_expr) => {
+                            return fn_expr
+                                .body
+                                .as_ref()
+                                .is_some_and(|body| has_react_return(body));
                         }
+                        _ => {}
                     }
                 }
             }
 
-            return false;
+            false
         }
         _ => false,
     }
@@ -164,6 +314,11 @@ fn get_component_name(node: &AstNode) -> Option<String> {
                 return Some(name.name.to_string());
             }
         }
+        AstKind::Class(class) => {
+            if let Some(name) = &class.id {
+                return Some(name.name.to_string());
+            }
+        }
         AstKind::VariableDeclaration(var_decl) => {
             let name = var_decl.declarations.first();
             if let Some(name) = name {
@@ -180,64 +335,113 @@ fn get_component_name(node: &AstNode) -> Option<String> {
     None
 }
 
-fn get_children<'a>(
-    node: &'a AstNode<'a>,
-    semantic: &'a Semantic<'a>,
-    resolver: &'a ProjectResolver,
-    file_path: PathBuf,
-) -> Vec<ComponentChild> {
+/// Get the kind of the react component and the wrappers applied to it
+fn get_component_kind(node: &AstNode) -> (ComponentKind, Vec<ComponentWrapper>) {
     match node.kind() {
+        AstKind::Class(_) => (ComponentKind::Class, Vec::new()),
         AstKind::VariableDeclaration(var_decl) => {
-            let name = var_decl.declarations.first();
-
-            if let Some(name) = name {
-                if let Some(init) = &name.init {
-                    match init {
-                        Expression::ArrowFunctionExpression(arrow_fn_expr) => {
-                            let body = &arrow_fn_expr.body;
+            let wrappers = var_decl
+                .declarations
+                .first()
+                .and_then(|declarator| declarator.init.as_ref())
+                .map(|init| unwrap_component_wrappers(init).1)
+                .unwrap_or_default();
+
+            (ComponentKind::Function, wrappers)
+        }
+        _ => (ComponentKind::Function, Vec::new()),
+    }
+}
 
-                            let child_components =
-                                traverse_body(semantic, body, resolver, file_path.clone());
+/// Get the body that renders the component's children.
+/// This is the function body for function components and the `render` method for class components.
+fn get_component_body<'a>(node: &AstNode<'a>) -> Option<&'a FunctionBody<'a>> {
+    match node.kind() {
+        AstKind::Function(fn_decl) => fn_decl.body.as_deref(),
+        AstKind::Class(class) => get_render_body(class),
+        AstKind::VariableDeclaration(var_decl) => {
+            let init = var_decl.declarations.first()?.init.as_ref()?;
+            let (inner, wrappers) = unwrap_component_wrappers(init);
 
-                            return child_components;
-                        }
-                        _ => {}
-                    }
-                }
+            // lazy components only point to a module, they don't render anything themselves
+            if wrappers.contains(&ComponentWrapper::Lazy) {
+                return None;
             }
-        }
-        AstKind::Function(fn_decl) => {
-            let body = &fn_decl.body;
 
-            if let Some(body) = body {
-                let child_components = traverse_body(semantic, body, resolver, file_path.clone());
-
-                return child_components;
+            match inner {
+                Expression::ArrowFunctionExpression(arrow_fn_expr) => Some(&arrow_fn_expr.body),
+                Expression::FunctionExpression(fn_expr) => fn_expr.body.as_deref(),
+                _ => None,
             }
         }
-        _ => {}
+        _ => None,
     }
+}
 
-    Vec::new()
+fn get_children<'a>(
+    node: &AstNode<'a>,
+    semantic: &'a Semantic<'a>,
+    resolver: &'a ProjectResolver,
+    package_resolver: &'a PackageResolver,
+    file_path: PathBuf,
+) -> (Vec<ComponentChild>, BTreeMap<String, Vec<SourceLocation>>) {
+    match get_component_body(node) {
+        Some(body) => traverse_body(semantic, body, resolver, package_resolver, file_path),
+        None => (Vec::new(), BTreeMap::new()),
+    }
 }
 
 /// find react components in ast with oxc
 pub fn extract_components<'a>(
     semantic: &'a Semantic<'a>,
     resolver: &'a ProjectResolver,
+    package_resolver: &'a PackageResolver,
     file_path: PathBuf,
 ) -> Vec<ComponentRoot> {
     let mut components: Vec<ComponentRoot> = Vec::new();
+    // Name and span of the declaration of each extracted component
+    let mut declarations: Vec<(String, Span)> = Vec::new();
 
     for node in semantic.nodes().iter() {
         if is_react_component(node) {
-            let name = get_component_name(node);
-            let children = get_children(node, semantic, resolver, file_path.clone());
+            let name = get_component_name(node).unwrap();
+            let span = node.kind().span();
+
+            // Named function expressions inside wrappers, e.g. `memo(function Button() {})`,
+            // are already covered by the variable declaration that holds the wrapper.
+            // Other declarations with the same name, e.g. in another scope, are kept.
+            if declarations.iter().any(|(declared, declaration)| {
+                *declared == name && declaration.start <= span.start && span.end <= declaration.end
+            }) {
+                continue;
+            }
+            declarations.push((name.clone(), span));
+
+            let (children, intrinsic_element_locations) = get_children(
+                node,
+                semantic,
+                resolver,
+                package_resolver,
+                file_path.clone(),
+            );
+
+            let declared_props = extract_declared_props(semantic, node);
+            let (kind, wrappers) = get_component_kind(node);
+            let location = SourceLocation::from_offset(semantic.source_text(), span.start);
 
             let component = ComponentRoot {
-                name: name.unwrap(),
-                props: HashMap::new(),
-                children: children,
+                name,
+                props: BTreeMap::new(),
+                declared_props,
+                kind,
+                wrappers,
+                location,
+                children,
+                intrinsic_elements: intrinsic_element_locations
+                    .iter()
+                    .map(|(element, locations)| (element.clone(), locations.len()))
+                    .collect(),
+                intrinsic_element_locations,
             };
 
             components.push(component);
@@ -253,121 +457,478 @@ struct ReturnVisitor<'a> {
     file_path: PathBuf,
     parent_file_path: PathBuf,
     child_components: Vec<ComponentChild>,
-    package_resolver: PackageResolver,
+    /// Where each intrinsic element is rendered
+    intrinsic_elements: BTreeMap<String, Vec<SourceLocation>>,
+    package_resolver: &'a PackageResolver,
 }
 
 impl<'a> ReturnVisitor<'a> {
-    fn new(semantic: &'a Semantic<'a>, resolver: &'a ProjectResolver, file_path: PathBuf) -> Self {
+    fn new(
+        semantic: &'a Semantic<'a>,
+        resolver: &'a ProjectResolver,
+        package_resolver: &'a PackageResolver,
+        file_path: PathBuf,
+    ) -> Self {
         Self {
             semantic,
             resolver,
             file_path: file_path.clone(),
             parent_file_path: file_path.parent().unwrap().to_path_buf(),
             child_components: Vec::new(),
-            package_resolver: PackageResolver::new(),
+            intrinsic_elements: BTreeMap::new(),
+            package_resolver,
         }
     }
+
+    fn create_child(&self, name: String) -> ComponentChild {
+        ComponentChild {
+            name,
+            props: BTreeMap::new(),
+            prop_values: BTreeMap::new(),
+            origin_file_path: PathBuf::new(),
+            project_name: None,
+            resolved_name: None,
+            package_version: None,
+            location: SourceLocation::default(),
+            spreads: Vec::new(),
+        }
+    }
+
+    /// Sets the origin of the child and tries to get the project name from the package.json.
+    /// Components of installed packages get the package name and version.
+    fn set_origin(&mut self, component_child: &mut ComponentChild, origin_file_path: PathBuf) {
+        let package = origin_file_path
+            .is_relative()
+            .then(|| {
+                self.resolver
+                    .modules()
+                    .get_package(&origin_file_path.to_string_lossy())
+            })
+            .flatten();
+
+        if let Some(package) = package {
+            component_child.project_name = Some(package.name);
+            component_child.package_version = package.version;
+        } else if let Ok(project_name) = self.package_resolver.get_package_name(&origin_file_path) {
+            component_child.project_name = Some(project_name);
+        }
+        component_child.origin_file_path = origin_file_path;
+    }
+
+    /// Records a spread attribute like `{...props}` on the child.
+    /// The keys of object literals and local const objects are counted as props.
+    fn add_spread(&self, component_child: &mut ComponentChild, argument: &Expression<'a>) {
+        let source_text = self.semantic.source_text();
+
+        let object = match argument {
+            // {...{ variant: 'primary' }}
+            Expression::ObjectExpression(object) => Some(object.as_ref()),
+            // const buttonProps = { variant: 'primary' }; {...buttonProps}
+            Expression::Identifier(identifier) => self.find_const_object(identifier),
+            _ => None,
+        };
+
+        let resolved = match object {
+            Some(object) => fold_object_props(source_text, component_child, object),
+            None => false,
+        };
+
+        component_child.spreads.push(PropSpread {
+            source: argument.span().source_text(source_text).to_string(),
+            resolved,
+        });
+    }
+
+    /// Finds the object literal a local const is initialized with
+    fn find_const_object(
+        &self,
+        identifier: &IdentifierReference<'a>,
+    ) -> Option<&'a ObjectExpression<'a>> {
+        let symbol_id = self
+            .semantic
+            .symbols()
+            .get_reference(identifier.reference_id())
+            .symbol_id()?;
+        let declaration = self.semantic.symbols().get_declaration(symbol_id);
+
+        match self.semantic.nodes().get_node(declaration).kind() {
+            AstKind::VariableDeclarator(declarator)
+                if declarator.kind.is_const()
+                    && matches!(declarator.id.kind, BindingPatternKind::BindingIdentifier(_)) =>
+            {
+                match &declarator.init {
+                    Some(Expression::ObjectExpression(object)) => Some(object.as_ref()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Records a rendering of a native element like `<div />`
+    fn add_intrinsic_element(&mut self, name: String, span: Span) {
+        let location = SourceLocation::from_offset(self.semantic.source_text(), span.start);
+        self.intrinsic_elements
+            .entry(name)
+            .or_default()
+            .push(location);
+    }
+
+    /// Resolves elements like `<Button />`.
+    /// Returns `None` if the element is not a component, e.g. a component passed in via props.
+    fn resolve_identifier_element(
+        &mut self,
+        identifier: &IdentifierReference<'a>,
+    ) -> Option<ComponentChild> {
+        let ident_name = identifier.name.to_string();
+        let mut component_child = self.create_child(ident_name.clone());
+
+        let reference_id = self
+            .semantic
+            .symbols()
+            .get_reference(identifier.reference_id());
+
+        let Some(symbol_id) = reference_id.symbol_id() else {
+            return Some(component_child);
+        };
+
+        let import_node_id = find_import_for_symbol(self.semantic, symbol_id);
+
+        if let Ok(import_node_id) = import_node_id {
+            let component_root = find_component_root(
+                self.semantic,
+                self.resolver,
+                &self.parent_file_path,
+                import_node_id,
+                &ident_name,
+            );
+
+            // a namespace is not a component, e.g. `<UI />` for `export * as UI from './ui'`
+            let component_root = component_root.filter(|(name, _)| name != NAMESPACE);
+
+            if let Some((component_name, origin_file_path)) = component_root {
+                // default and aliased imports are registered under the name of the component
+                if component_name != ident_name && component_name != "default" {
+                    component_child.resolved_name = Some(component_name);
+                }
+                self.set_origin(&mut component_child, origin_file_path);
+            }
+        } else {
+            let declaration = self.semantic.symbols().get_declaration(symbol_id);
+            let declaration_node = self.semantic.nodes().get_node(declaration);
+
+            if is_react_component(declaration_node) {
+                self.set_origin(&mut component_child, self.file_path.clone());
+            }
+
+            if let AstKind::FormalParameter(_) = declaration_node.kind() {
+                return None;
+            }
+        }
+
+        Some(component_child)
+    }
+
+    /// Resolves elements like `<Tabs.Panel />` or `<UI.Button />`.
+    /// The child keeps the full dotted name. Its origin is the module of the object,
+    /// or the file of the static property component if that can be resolved.
+    fn resolve_member_element(
+        &mut self,
+        member_expr: &JSXMemberExpression<'a>,
+    ) -> Option<ComponentChild> {
+        let (object, properties) = flatten_member_expression(member_expr)?;
+        let name = format!("{}.{}", object.name, properties.join("."));
+        let mut component_child = self.create_child(name);
+
+        let reference_id = self.semantic.symbols().get_reference(object.reference_id());
+
+        let Some(symbol_id) = reference_id.symbol_id() else {
+            return Some(component_child);
+        };
+
+        let declaration = self.semantic.symbols().get_declaration(symbol_id);
+        let declaration_node = self.semantic.nodes().get_node(declaration);
+
+        if let AstKind::FormalParameter(_) = declaration_node.kind() {
+            return None;
+        }
+
+        let mut properties = properties.into_iter();
+
+        let component_root = match find_import_for_symbol(self.semantic, symbol_id) {
+            // `import * as UI from './ui'` makes the first property the imported component
+            Ok(import_node_id)
+                if matches!(
+                    declaration_node.kind(),
+                    AstKind::ImportNamespaceSpecifier(_)
+                ) =>
+            {
+                let property = properties.next()?;
+
+                find_component_root(
+                    self.semantic,
+                    self.resolver,
+                    &self.parent_file_path,
+                    import_node_id,
+                    property,
+                )
+            }
+            Ok(import_node_id) => find_component_root(
+                self.semantic,
+                self.resolver,
+                &self.parent_file_path,
+                import_node_id,
+                &object.name,
+            ),
+            Err(_) => Some((object.name.to_string(), self.file_path.clone())),
+        };
+
+        let Some((mut component_name, mut origin_file_path)) = component_root else {
+            return Some(component_child);
+        };
+
+        for property in properties {
+            let property_root = if component_name == NAMESPACE {
+                find_namespace_member(self.resolver, &origin_file_path, property)
+            } else {
+                find_static_property_root(
+                    self.resolver,
+                    &origin_file_path,
+                    &component_name,
+                    property,
+                )
+            };
+
+            match property_root {
+                Some((property_name, property_file_path)) => {
+                    component_name = property_name;
+                    origin_file_path = property_file_path;
+                }
+                None => {
+                    self.set_origin(&mut component_child, origin_file_path);
+                    return Some(component_child);
+                }
+            }
+        }
+
+        if component_name == NAMESPACE {
+            return Some(component_child);
+        }
+
+        component_child.resolved_name = Some(component_name);
+        self.set_origin(&mut component_child, origin_file_path);
+
+        Some(component_child)
+    }
+}
+
+/// Records a prop passed to the child together with its value
+fn add_prop(component_child: &mut ComponentChild, name: &str, kind: PropValueKind, value: &str) {
+    component_child
+        .prop_values
+        .entry(name.to_string())
+        .or_default()
+        .add(kind, value);
+
+    component_child.props.insert(name.to_string(), 1);
+}
+
+/// Counts the keys of a spread object as props of the child.
+/// Returns `false` if some keys are unknown, e.g. because of computed keys or nested spreads.
+fn fold_object_props(
+    source_text: &str,
+    component_child: &mut ComponentChild,
+    object: &ObjectExpression,
+) -> bool {
+    let mut resolved = true;
+
+    for property in &object.properties {
+        match property {
+            ObjectPropertyKind::ObjectProperty(property) if !property.computed => {
+                match property.key.static_name() {
+                    Some(name) => {
+                        let (kind, value) = classify_expression(source_text, &property.value);
+                        add_prop(component_child, &name, kind, &value);
+                    }
+                    None => resolved = false,
+                }
+            }
+            _ => resolved = false,
+        }
+    }
+
+    resolved
+}
+
+/// Classifies the value of a JSX attribute.
+/// Returns the kind of the value and the value itself if it is known statically.
+fn classify_attribute_value(
+    source_text: &str,
+    value: Option<&JSXAttributeValue>,
+) -> (PropValueKind, String) {
+    match value {
+        // <Button disabled />
+        None => (PropValueKind::Boolean, "true".to_string()),
+        Some(JSXAttributeValue::StringLiteral(literal)) => {
+            (PropValueKind::String, literal.value.to_string())
+        }
+        Some(JSXAttributeValue::ExpressionContainer(container)) => {
+            match container.expression.as_expression() {
+                Some(expression) => classify_expression(source_text, expression),
+                None => dynamic_value(),
+            }
+        }
+        Some(_) => dynamic_value(),
+    }
+}
+
+/// Classifies an expression passed as a prop value
+fn classify_expression(source_text: &str, expression: &Expression) -> (PropValueKind, String) {
+    match expression {
+        Expression::StringLiteral(literal) => (PropValueKind::String, literal.value.to_string()),
+        Expression::NumericLiteral(_) => (
+            PropValueKind::Number,
+            expression.span().source_text(source_text).to_string(),
+        ),
+        // {-1}
+        Expression::UnaryExpression(unary)
+            if matches!(unary.argument, Expression::NumericLiteral(_)) =>
+        {
+            (
+                PropValueKind::Number,
+                expression.span().source_text(source_text).to_string(),
+            )
+        }
+        Expression::BooleanLiteral(literal) => (PropValueKind::Boolean, literal.value.to_string()),
+        // {Variant.Primary}
+        Expression::StaticMemberExpression(_) if is_enum_like(expression) => (
+            PropValueKind::Enum,
+            expression.span().source_text(source_text).to_string(),
+        ),
+        // {`primary`} is known statically, {`size-${size}`} is not
+        Expression::TemplateLiteral(template) => match template.quasis.first() {
+            Some(quasi) if template.expressions.is_empty() => {
+                (PropValueKind::Template, quasi.value.raw.to_string())
+            }
+            _ => (
+                PropValueKind::Template,
+                PropValueStats::DYNAMIC_VALUE.to_string(),
+            ),
+        },
+        Expression::ParenthesizedExpression(parenthesized) => {
+            classify_expression(source_text, &parenthesized.expression)
+        }
+        _ => dynamic_value(),
+    }
+}
+
+/// Check if the expression is a chain of static member accesses on a pascal case identifier,
+/// e.g. `Theme.Colors.Primary`. Accesses like `props.variant` are not enum-like.
+fn is_enum_like(expression: &Expression) -> bool {
+    match expression {
+        Expression::Identifier(identifier) => has_correct_case(&identifier.name),
+        Expression::StaticMemberExpression(member_expr) => is_enum_like(&member_expr.object),
+        _ => false,
+    }
+}
+
+fn dynamic_value() -> (PropValueKind, String) {
+    (
+        PropValueKind::Dynamic,
+        PropValueStats::DYNAMIC_VALUE.to_string(),
+    )
+}
+
+/// Splits `<A.B.C />` into the object identifier `A` and the properties `["B", "C"]`.
+/// Returns `None` for member expressions on `this`.
+fn flatten_member_expression<'b, 'a>(
+    member_expr: &'b JSXMemberExpression<'a>,
+) -> Option<(&'b IdentifierReference<'a>, Vec<&'b str>)> {
+    let (object, mut properties) = match &member_expr.object {
+        JSXMemberExpressionObject::IdentifierReference(identifier) => {
+            (identifier.as_ref(), Vec::new())
+        }
+        JSXMemberExpressionObject::MemberExpression(inner) => flatten_member_expression(inner)?,
+        JSXMemberExpressionObject::ThisExpression(_) => return None,
+    };
+
+    properties.push(member_expr.property.name.as_str());
+
+    Some((object, properties))
 }
 
 impl<'a> Visit<'a> for ReturnVisitor<'a> {
     fn visit_jsx_opening_element(&mut self, jsx_opening_element: &JSXOpeningElement<'a>) {
-        match &jsx_opening_element.name {
+        let component_child = match &jsx_opening_element.name {
             JSXElementName::IdentifierReference(identifier) => {
-                let ident_name = &identifier.name;
-                let ident_name = ident_name.to_string();
-
-                let mut component_child = ComponentChild {
-                    name: ident_name.clone(),
-                    props: HashMap::new(),
-                    origin_file_path: PathBuf::new(),
-                    project_name: None,
-                };
+                self.resolve_identifier_element(identifier)
+            }
+            JSXElementName::MemberExpression(member_expr) => {
+                self.resolve_member_element(member_expr)
+            }
+            // <div />
+            JSXElementName::Identifier(identifier) => {
+                self.add_intrinsic_element(identifier.name.to_string(), jsx_opening_element.span);
+                None
+            }
+            // <svg:rect />
+            JSXElementName::NamespacedName(namespaced_name) => {
+                self.add_intrinsic_element(namespaced_name.to_string(), jsx_opening_element.span);
+                None
+            }
+            _ => None,
+        };
 
-                let reference_id = self
-                    .semantic
-                    .symbols()
-                    .get_reference(identifier.reference_id());
-
-                if let Some(symbol_id) = reference_id.symbol_id() {
-                    let import_node_id = find_import_for_symbol(self.semantic, symbol_id);
-
-                    if let Ok(import_node_id) = import_node_id {
-                        let component_root = find_component_root(
-                            self.semantic,
-                            self.resolver,
-                            &self.parent_file_path,
-                            import_node_id,
-                            &ident_name,
-                        );
-
-                        if let Some(component_root) = component_root {
-                            component_child.origin_file_path = component_root.1.clone();
-                            // Try to get the project name from the package.json
-                            if let Ok(project_name) =
-                                (&mut self.package_resolver).get_package_name(&component_root.1)
-                            {
-                                component_child.project_name = Some(project_name);
-                            }
-                        }
-                    } else {
-                        let declaration = self.semantic.symbols().get_declaration(symbol_id);
-                        let declaration_node = self.semantic.nodes().get_node(declaration);
-
-                        if is_react_component(declaration_node) {
-                            component_child.origin_file_path = self.file_path.clone();
-                            // Try to get the project name from the package.json for local components
-                            if let Ok(project_name) =
-                                (&mut self.package_resolver).get_package_name(&self.file_path)
-                            {
-                                component_child.project_name = Some(project_name);
-                            }
-                        }
+        let Some(mut component_child) = component_child else {
+            return;
+        };
 
-                        if let AstKind::FormalParameter(_) = declaration_node.kind() {
-                            return;
-                        }
-                    }
-                }
+        component_child.location = SourceLocation::from_offset(
+            self.semantic.source_text(),
+            jsx_opening_element.span.start,
+        );
 
-                jsx_opening_element
-                    .attributes
-                    .iter()
-                    .for_each(|attribute| match attribute {
-                        JSXAttributeItem::Attribute(jsx_attribute) => {
-                            let attribute_name = &jsx_attribute.name;
+        let source_text = self.semantic.source_text();
 
-                            if let JSXAttributeName::Identifier(identifier) = attribute_name {
-                                let ident_name = &identifier.name;
-                                let ident_name = ident_name.to_string();
+        for attribute in &jsx_opening_element.attributes {
+            match attribute {
+                JSXAttributeItem::Attribute(jsx_attribute) => {
+                    let attribute_name = &jsx_attribute.name;
 
-                                component_child.props.insert(ident_name, 1);
-                            }
-                        }
-                        JSXAttributeItem::SpreadAttribute(_) => {}
-                    });
+                    if let JSXAttributeName::Identifier(identifier) = attribute_name {
+                        let (kind, value) =
+                            classify_attribute_value(source_text, jsx_attribute.value.as_ref());
 
-                self.child_components.push(component_child);
+                        add_prop(&mut component_child, &identifier.name, kind, &value);
+                    }
+                }
+                JSXAttributeItem::SpreadAttribute(spread_attribute) => {
+                    self.add_spread(&mut component_child, &spread_attribute.argument);
+                }
             }
-            _ => {}
         }
+
+        self.child_components.push(component_child);
     }
 }
 
+/// Collects the components and the intrinsic elements rendered in a function body
 pub fn traverse_body<'a>(
     semantic: &'a Semantic<'a>,
     body: &'a FunctionBody<'a>,
     resolver: &'a ProjectResolver,
+    package_resolver: &'a PackageResolver,
     file_path: PathBuf,
-) -> Vec<ComponentChild> {
-    let mut visitor = ReturnVisitor::new(semantic, resolver, file_path);
+) -> (Vec<ComponentChild>, BTreeMap<String, Vec<SourceLocation>>) {
+    let mut visitor = ReturnVisitor::new(semantic, resolver, package_resolver, file_path);
     visitor.visit_function_body(body);
 
-    visitor.child_components
+    (visitor.child_components, visitor.intrinsic_elements)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{collections::BTreeMap, path::PathBuf};
 
     use oxc_allocator::Allocator;
     use oxc_parser::Parser;
@@ -375,7 +936,13 @@ mod tests {
     use oxc_span::SourceType;
 
     use crate::{
-        analyze::react::root_components::extract_components, traverse::ProjectResolver,
+        analyze::{
+            component::{
+                ComponentKind, ComponentWrapper, PropValueKind, PropValueStats, SourceLocation,
+            },
+            react::root_components::extract_components,
+        },
+        traverse::{PackageResolver, ProjectResolver},
         util::test_utils::create_mock_project,
     };
 
@@ -410,6 +977,7 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
@@ -450,6 +1018,7 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
@@ -492,6 +1061,7 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
@@ -534,6 +1104,7 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
@@ -576,6 +1147,7 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
@@ -610,6 +1182,7 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
@@ -640,10 +1213,565 @@ mod tests {
         let components = extract_components(
             &semantic.semantic,
             &ProjectResolver::new(None),
+            &PackageResolver::new(),
             PathBuf::from(temp_dir.path().join("src/components/Button.tsx")),
         );
 
         assert_eq!(components[0].name, "Button");
         assert_eq!(components[0].children.len(), 0);
     }
+
+    #[test]
+    fn test_find_class_components() {
+        let files = vec![
+            (
+                "src/components/Counter.tsx",
+                r#"
+                import React, { PureComponent } from 'react';
+                import { Button } from './Button';
+
+                class Counter extends React.Component {
+                    render() {
+                        return <Button label="Increment" />;
+                    }
+                }
+
+                class Display extends PureComponent {
+                    render() {
+                        return <span />;
+                    }
+                }
+
+                class Store extends BaseStore {}
+            "#,
+            ),
+            (
+                "src/components/Button.tsx",
+                r#"
+                export const Button = () => <button />;
+            "#,
+            ),
+        ];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Counter.tsx"),
+        );
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].name, "Counter");
+        assert_eq!(components[0].kind, ComponentKind::Class);
+        assert_eq!(components[0].children[0].name, "Button");
+        assert_eq!(
+            components[0].children[0].origin_file_path,
+            temp_dir.path().join("src/components/Button.tsx")
+        );
+        assert_eq!(components[1].name, "Display");
+        assert_eq!(components[1].kind, ComponentKind::Class);
+    }
+
+    #[test]
+    fn test_find_wrapped_components() {
+        let files = vec![(
+            "src/components/Input.tsx",
+            r#"
+                import React, { memo, forwardRef } from 'react';
+
+                export const Label = React.memo(({ text }) => <label>{text}</label>);
+
+                export const Input = memo(forwardRef((props, ref) => {
+                    return <div><Label text="Name" /><input ref={ref} /></div>;
+                }));
+
+                export const Field = memo(function Field() {
+                    return <Input />;
+                });
+
+                const Settings = React.lazy(() => import('./Settings'));
+            "#,
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Input.tsx"),
+        );
+
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Label", "Input", "Field", "Settings"]);
+
+        assert_eq!(components[0].wrappers, vec![ComponentWrapper::Memo]);
+        assert_eq!(
+            components[1].wrappers,
+            vec![ComponentWrapper::Memo, ComponentWrapper::ForwardRef]
+        );
+        assert_eq!(components[1].children[0].name, "Label");
+        assert_eq!(components[2].wrappers, vec![ComponentWrapper::Memo]);
+        assert_eq!(components[2].children[0].name, "Input");
+        assert_eq!(components[3].wrappers, vec![ComponentWrapper::Lazy]);
+        assert!(components[3].children.is_empty());
+    }
+
+    #[test]
+    fn test_keep_components_with_the_same_name() {
+        let files = vec![(
+            "src/components/Button.tsx",
+            r#"
+                export namespace UI {
+                    export function Button() {
+                        return <button />;
+                    }
+                }
+
+                export function Toolbar() {
+                    function Button() {
+                        return <a />;
+                    }
+
+                    return <Button />;
+                }
+            "#,
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Button.tsx"),
+        );
+
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Button", "Toolbar", "Button"]);
+        assert_eq!(components[0].intrinsic_elements.get("button"), Some(&1));
+        assert_eq!(components[2].intrinsic_elements.get("a"), Some(&1));
+    }
+
+    #[test]
+    fn test_find_member_expression_components() {
+        let files = vec![
+            (
+                "src/components/Page.tsx",
+                r#"
+                import * as UI from './ui';
+                import { Tabs } from './Tabs';
+                import { Form } from './Form';
+
+                const Page = ({ Layout }) => {
+                    return <Layout.Main>
+                        <Tabs.Panel title="Overview" />
+                        <Form.Field name="email" />
+                        <Form.Label />
+                        <UI.Button label="Save" />
+                    </Layout.Main>;
+                }
+            "#,
+            ),
+            (
+                "src/components/Tabs.tsx",
+                r#"
+                import { TabPanel } from './TabPanel';
+
+                export const Tabs = () => <div />;
+
+                Tabs.Panel = TabPanel;
+            "#,
+            ),
+            (
+                "src/components/TabPanel.tsx",
+                r#"
+                export const TabPanel = () => <div />;
+            "#,
+            ),
+            (
+                "src/components/Form.tsx",
+                r#"
+                const FormRoot = () => <form />;
+                const FormField = () => <input />;
+
+                export const Form = Object.assign(FormRoot, { Field: FormField });
+            "#,
+            ),
+            (
+                "src/components/ui/index.tsx",
+                r#"
+                export { Button } from './Button';
+            "#,
+            ),
+            (
+                "src/components/ui/Button.tsx",
+                r#"
+                export const Button = () => <button />;
+            "#,
+            ),
+        ];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Page.tsx"),
+        );
+
+        let children = &components[0].children;
+        let names: Vec<&str> = children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Tabs.Panel", "Form.Field", "Form.Label", "UI.Button"]
+        );
+
+        assert_eq!(children[0].resolved_name, Some("TabPanel".to_string()));
+        assert_eq!(
+            children[0].origin_file_path,
+            temp_dir.path().join("src/components/TabPanel.tsx")
+        );
+        assert_eq!(children[0].props.get("title"), Some(&1));
+
+        assert_eq!(children[1].resolved_name, Some("FormField".to_string()));
+        assert_eq!(
+            children[1].origin_file_path,
+            temp_dir.path().join("src/components/Form.tsx")
+        );
+
+        // unknown static properties stay linked to the object's module
+        assert_eq!(children[2].resolved_name, None);
+        assert_eq!(children[2].component_name(), "Form.Label");
+        assert_eq!(
+            children[2].origin_file_path,
+            temp_dir.path().join("src/components/Form.tsx")
+        );
+
+        assert_eq!(children[3].resolved_name, Some("Button".to_string()));
+        assert_eq!(
+            children[3].origin_file_path,
+            temp_dir.path().join("src/components/ui/Button.tsx")
+        );
+    }
+
+    #[test]
+    fn test_find_namespace_reexport_components() {
+        let files = vec![
+            (
+                "src/components/Page.tsx",
+                r#"
+                import { Panels } from './index';
+                import Card from './Card';
+
+                const Page = () => {
+                    return <div>
+                        <Panels.TabPanel title="Overview" />
+                        <Card />
+                    </div>;
+                }
+            "#,
+            ),
+            (
+                "src/components/index.ts",
+                r#"
+                export * as Panels from './TabPanel';
+            "#,
+            ),
+            (
+                "src/components/TabPanel.tsx",
+                r#"
+                export const TabPanel = () => <div />;
+            "#,
+            ),
+            (
+                "src/components/Card.tsx",
+                r#"
+                export default () => <div />;
+            "#,
+            ),
+        ];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Page.tsx"),
+        );
+
+        let children = &components[0].children;
+        assert_eq!(children[0].name, "Panels.TabPanel");
+        assert_eq!(children[0].component_name(), "TabPanel");
+        assert_eq!(
+            children[0].origin_file_path,
+            temp_dir.path().join("src/components/TabPanel.tsx")
+        );
+
+        // anonymous default exports keep the name they are imported by
+        assert_eq!(children[1].component_name(), "Card");
+        assert_eq!(
+            children[1].origin_file_path,
+            temp_dir.path().join("src/components/Card.tsx")
+        );
+    }
+
+    #[test]
+    fn test_find_components_with_nested_returns() {
+        let files = vec![(
+            "src/components/Status.tsx",
+            r#"
+                const Spinner = () => <span />;
+
+                const Loading = ({ loading }) => loading && <Spinner />;
+
+                function Status({ status }) {
+                    if (status === 'loading') {
+                        return (<Spinner />);
+                    }
+
+                    switch (status) {
+                        case 'error':
+                            return <Error />;
+                        default:
+                            return null;
+                    }
+                }
+
+                const Toggle = ({ on }) => {
+                    try {
+                        return on ? <On /> : <Off />;
+                    } catch (e) {
+                        return undefined;
+                    }
+                };
+
+                const Tracked = () => (track(), <div />);
+
+                function RenderProp() {
+                    const render = () => <li />;
+                    return render;
+                }
+            "#,
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Status.tsx"),
+        );
+
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Spinner", "Loading", "Status", "Toggle", "Tracked"]
+        );
+
+        let status_children: Vec<&str> = components[2]
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(status_children, vec!["Spinner", "Error"]);
+
+        let toggle_children: Vec<&str> = components[3]
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(toggle_children, vec!["On", "Off"]);
+    }
+
+    #[test]
+    fn test_intrinsic_elements() {
+        let files = vec![(
+            "src/components/Card.tsx",
+            r#"
+            import { Title } from './Title';
+
+            export function Card() {
+                return (
+                    <div>
+                        <Title />
+                        <svg:rect />
+                        <div>
+                            <button>Ok</button>
+                        </div>
+                    </div>
+                );
+            }
+            "#,
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Card.tsx"),
+        );
+
+        assert_eq!(components[0].children.len(), 1);
+        assert_eq!(
+            components[0].intrinsic_elements,
+            BTreeMap::from([
+                ("div".to_string(), 2),
+                ("button".to_string(), 1),
+                ("svg:rect".to_string(), 1)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_component_locations() {
+        let files = vec![(
+            "src/components/Card.tsx",
+            "import { Title } from './Title';\n\nexport function Card() {\n  return <div>\n    <Title />\n  </div>;\n}\n",
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Card.tsx"),
+        );
+
+        assert_eq!(
+            components[0].location,
+            SourceLocation { line: 3, column: 8 }
+        );
+        assert_eq!(
+            components[0].children[0].location,
+            SourceLocation { line: 5, column: 5 }
+        );
+        assert_eq!(
+            components[0].intrinsic_element_locations["div"],
+            vec![SourceLocation {
+                line: 4,
+                column: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn test_classify_prop_values() {
+        let files = vec![(
+            "src/components/Form.tsx",
+            r#"
+                function Form({ size, props }) {
+                    return <div>
+                        <Button variant="primary" size={12} disabled />
+                        <Button variant={'primary'} size={-1} disabled={false} />
+                        <Button variant={ButtonVariant.Secondary} size={`lg`} />
+                        <Button variant={props.variant} size={`size-${size}`} onClick={() => {}} />
+                    </div>;
+                }
+            "#,
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Form.tsx"),
+        );
+
+        let mut variants = PropValueStats::default();
+        let mut sizes = PropValueStats::default();
+        for child in &components[0].children {
+            variants.merge(&child.prop_values["variant"]);
+            sizes.merge(&child.prop_values["size"]);
+        }
+
+        assert_eq!(variants.values.get("primary"), Some(&2));
+        assert_eq!(variants.values.get("ButtonVariant.Secondary"), Some(&1));
+        assert_eq!(variants.values.get("<dynamic>"), Some(&1));
+        assert_eq!(variants.kinds.get(&PropValueKind::String), Some(&2));
+        assert_eq!(variants.kinds.get(&PropValueKind::Enum), Some(&1));
+        assert_eq!(variants.kinds.get(&PropValueKind::Dynamic), Some(&1));
+
+        assert_eq!(sizes.values.get("12"), Some(&1));
+        assert_eq!(sizes.values.get("-1"), Some(&1));
+        assert_eq!(sizes.values.get("lg"), Some(&1));
+        assert_eq!(sizes.values.get("<dynamic>"), Some(&1));
+        assert_eq!(sizes.kinds.get(&PropValueKind::Number), Some(&2));
+        assert_eq!(sizes.kinds.get(&PropValueKind::Template), Some(&2));
+
+        let disabled = &components[0].children[0].prop_values["disabled"];
+        assert_eq!(disabled.values.get("true"), Some(&1));
+        assert_eq!(
+            components[0].children[1].prop_values["disabled"]
+                .values
+                .get("false"),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn test_spread_attributes() {
+        let files = vec![(
+            "src/components/Form.tsx",
+            r#"
+                const defaults = { variant: 'primary', size: 12 };
+
+                function Form({ label, ...rest }) {
+                    return <div>
+                        <Button {...defaults} label={label} />
+                        <Button {...{ disabled: true, [label]: 1 }} />
+                        <Button {...rest} />
+                    </div>;
+                }
+            "#,
+        )];
+        let temp_dir = create_mock_project(&files);
+
+        let allocator = Allocator::default();
+        let semantic = setup_semantic(&allocator, files[0].1);
+        let components = extract_components(
+            &semantic.semantic,
+            &ProjectResolver::new(None),
+            &PackageResolver::new(),
+            temp_dir.path().join("src/components/Form.tsx"),
+        );
+
+        let children = &components[0].children;
+
+        assert_eq!(children[0].props.len(), 3);
+        assert_eq!(
+            children[0].prop_values["variant"].values.get("primary"),
+            Some(&1)
+        );
+        assert_eq!(children[0].spreads[0].source, "defaults");
+        assert!(children[0].spreads[0].resolved);
+        assert!(!children[0].usage().has_unknown_spread);
+
+        // the computed key can't be known, but the static key still counts
+        assert_eq!(children[1].props.get("disabled"), Some(&1));
+        assert!(!children[1].spreads[0].resolved);
+
+        assert!(children[2].props.is_empty());
+        assert_eq!(children[2].spreads[0].source, "rest");
+        assert!(children[2].usage().has_unknown_spread);
+    }
 }
@@ -0,0 +1,474 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use spinne_logger::Logger;
+
+use crate::{
+    analyze::component::Component,
+    traverse::{ProjectResolver, SourceDependency},
+};
+
+/// Name of the directory the analysis cache is stored in
+pub const CACHE_DIR: &str = ".spinne-cache";
+
+/// The components extracted from a single file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Hash of the file content
+    hash: String,
+    /// Hashes of the modules and `package.json` files read while resolving the children
+    dependencies: HashMap<PathBuf, String>,
+    /// Specifiers that could not be resolved, by the directory they were resolved from
+    #[serde(default)]
+    unresolved: Vec<(PathBuf, String)>,
+    components: Vec<Component>,
+}
+
+/// The content of a cache file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// The spinne version that wrote the cache
+    version: String,
+    /// Fingerprint of the project configuration that affects the analysis
+    fingerprint: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// Persistent cache of the components extracted from the files of a project.
+///
+/// Entries are keyed by the content hash of a file. They are invalidated if the file, a module or
+/// `package.json` read while resolving its components changes, or if a specifier that could not be
+/// resolved resolves now. The whole cache is invalidated if the spinne version or the resolver
+/// configuration (`tsconfig.json`, `package.json`) of the project changes.
+#[derive(Debug)]
+pub struct AnalysisCache {
+    path: PathBuf,
+    fingerprint: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl AnalysisCache {
+    /// Loads the cache of a project from the cache directory.
+    /// Returns an empty cache if there is none or it was written for a different configuration.
+    pub fn load(cache_dir: &Path, project_name: &str, project_root: &Path) -> Self {
+        let path = cache_dir.join(format!("{}.json", hash_content(project_name.as_bytes())));
+        let fingerprint = Self::fingerprint(project_root);
+
+        let entries = fs::read(&path)
+            .ok()
+            .and_then(
+                |content| match serde_json::from_slice::<CacheFile>(&content) {
+                    Ok(cache_file) => Some(cache_file),
+                    Err(e) => {
+                        Logger::warn(&format!("Ignoring invalid cache {}: {}", path.display(), e));
+                        None
+                    }
+                },
+            )
+            .filter(|cache_file| {
+                cache_file.version == env!("CARGO_PKG_VERSION")
+                    && cache_file.fingerprint == fingerprint
+            })
+            .map(|cache_file| cache_file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            fingerprint,
+            entries,
+        }
+    }
+
+    /// Gets the components of a file if neither the file nor its dependencies changed.
+    /// `hashes` holds the content hashes of the files of the current run.
+    pub fn get(
+        &self,
+        path: &Path,
+        hash: &str,
+        hashes: &HashMap<PathBuf, String>,
+        resolver: &ProjectResolver,
+    ) -> Option<Vec<Component>> {
+        let entry = self.entries.get(path)?;
+
+        if entry.hash != hash {
+            return None;
+        }
+
+        let dependencies_unchanged =
+            entry
+                .dependencies
+                .iter()
+                .all(|(dependency, hash)| match hashes.get(dependency) {
+                    Some(current) => current == hash,
+                    None => hash_file(dependency).is_some_and(|current| &current == hash),
+                });
+        let still_unresolved = || {
+            entry
+                .unresolved
+                .iter()
+                .all(|(directory, specifier)| resolver.resolve(directory, specifier).is_err())
+        };
+
+        (dependencies_unchanged && still_unresolved()).then(|| entry.components.clone())
+    }
+
+    /// Stores the components of a file with the dependencies recorded while analyzing it
+    pub(crate) fn insert(
+        &mut self,
+        path: PathBuf,
+        hash: String,
+        components: Vec<Component>,
+        recorded: &[SourceDependency],
+        hashes: &HashMap<PathBuf, String>,
+    ) {
+        let mut dependencies = HashMap::new();
+        let mut unresolved = Vec::new();
+
+        for dependency in recorded {
+            match dependency {
+                SourceDependency::File(file) if *file != path => {
+                    // a file that can't be read is treated as changed on the next run
+                    let hash = hashes
+                        .get(file)
+                        .cloned()
+                        .or_else(|| hash_file(file))
+                        .unwrap_or_default();
+                    dependencies.insert(file.clone(), hash);
+                }
+                SourceDependency::File(_) => {}
+                SourceDependency::Unresolved {
+                    directory,
+                    specifier,
+                } => unresolved.push((directory.clone(), specifier.clone())),
+            }
+        }
+
+        self.entries.insert(
+            path,
+            CacheEntry {
+                hash,
+                dependencies,
+                unresolved,
+                components,
+            },
+        );
+    }
+
+    /// Removes the entries of files that are not part of the project anymore
+    pub fn retain(&mut self, files: &[PathBuf]) {
+        let files: HashSet<&PathBuf> = files.iter().collect();
+        self.entries.retain(|path, _| files.contains(path));
+    }
+
+    /// Writes the cache to the cache directory
+    pub fn save(&self) {
+        let cache_file = CacheFile {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            fingerprint: self.fingerprint.clone(),
+            entries: self.entries.clone(),
+        };
+
+        let result = self
+            .path
+            .parent()
+            .map_or(Ok(()), Self::create_dir)
+            .and_then(|_| {
+                let content = serde_json::to_vec(&cache_file)?;
+                fs::write(&self.path, content)
+            });
+
+        if let Err(e) = result {
+            Logger::warn(&format!(
+                "Failed to write cache {}: {}",
+                self.path.display(),
+                e
+            ));
+        }
+    }
+
+    /// Removes the cache directory with the caches of all projects
+    pub fn clear(cache_dir: &Path) -> std::io::Result<()> {
+        if cache_dir.exists() {
+            fs::remove_dir_all(cache_dir)?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the cache directory. It ignores its own content so it is never committed.
+    fn create_dir(cache_dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(cache_dir)?;
+
+        let gitignore = cache_dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(gitignore, "*\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Computes a fingerprint of the project location and the configuration the resolver reads
+    fn fingerprint(project_root: &Path) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(project_root.to_string_lossy().as_bytes());
+
+        for file in ["tsconfig.json", "package.json"] {
+            hasher.update(file.as_bytes());
+            if let Ok(content) = fs::read(project_root.join(file)) {
+                hasher.update(content);
+            }
+        }
+
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Computes the hash of a file's content
+pub fn hash_file(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|content| hash_content(&content))
+}
+
+fn hash_content(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::{analyze::component::ComponentChild, util::test_utils::create_mock_project};
+
+    use super::*;
+
+    fn component(file_path: &Path, child_path: &Path) -> Component {
+        let child = ComponentChild {
+            name: "Button".to_string(),
+            props: BTreeMap::new(),
+            prop_values: BTreeMap::new(),
+            origin_file_path: child_path.to_path_buf(),
+            project_name: None,
+            resolved_name: None,
+            package_version: None,
+            location: Default::default(),
+            spreads: Vec::new(),
+        };
+
+        Component::new(
+            "App".to_string(),
+            file_path.to_path_buf(),
+            BTreeMap::new(),
+            vec![child],
+        )
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let temp_dir = create_mock_project(&vec![
+            ("package.json", r#"{"name": "app"}"#),
+            ("src/App.tsx", "export const App = () => <Button />;"),
+            ("src/Button.tsx", "export const Button = () => <button />;"),
+        ]);
+        let root = temp_dir.path();
+        let cache_dir = root.join(CACHE_DIR);
+        let app = root.join("src/App.tsx");
+        let button = root.join("src/Button.tsx");
+        let hashes = HashMap::from([
+            (app.clone(), hash_file(&app).unwrap()),
+            (button.clone(), hash_file(&button).unwrap()),
+        ]);
+
+        let resolver = ProjectResolver::new(None);
+        let missing = (root.join("src"), "./Card".to_string());
+
+        let mut cache = AnalysisCache::load(&cache_dir, "app", root);
+        assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_none());
+
+        cache.insert(
+            app.clone(),
+            hashes[&app].clone(),
+            vec![component(&app, &button)],
+            &[
+                SourceDependency::File(button.clone()),
+                SourceDependency::Unresolved {
+                    directory: missing.0.clone(),
+                    specifier: missing.1.clone(),
+                },
+            ],
+            &hashes,
+        );
+        cache.save();
+        assert!(cache_dir.join(".gitignore").exists());
+
+        let cache = AnalysisCache::load(&cache_dir, "app", root);
+        let components = cache.get(&app, &hashes[&app], &hashes, &resolver).unwrap();
+        assert_eq!(components[0].name, "App");
+
+        // a changed file is analyzed again
+        assert!(cache.get(&app, "changed", &hashes, &resolver).is_none());
+
+        // a changed dependency invalidates the entry
+        let mut changed_hashes = hashes.clone();
+        changed_hashes.insert(button.clone(), "changed".to_string());
+        assert!(cache
+            .get(&app, &hashes[&app], &changed_hashes, &resolver)
+            .is_none());
+
+        // a specifier that resolves now invalidates the entry
+        fs::write(
+            root.join("src/Card.tsx"),
+            "export const Card = () => <div />;",
+        )
+        .unwrap();
+        assert!(cache
+            .get(&app, &hashes[&app], &hashes, &ProjectResolver::new(None))
+            .is_none());
+
+        // a changed configuration invalidates the whole cache
+        fs::write(root.join("tsconfig.json"), "{}").unwrap();
+        let cache = AnalysisCache::load(&cache_dir, "app", root);
+        assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_none());
+
+        AnalysisCache::clear(&cache_dir).unwrap();
+        assert!(!cache_dir.exists());
+    }
+
+    fn mock_project() -> (tempfile::TempDir, HashMap<PathBuf, String>) {
+        let temp_dir = create_mock_project(&vec![
+            ("package.json", r#"{"name": "app"}"#),
+            ("src/App.tsx", "export const App = () => <Button />;"),
+            ("src/Button.tsx", "export const Button = () => <button />;"),
+        ]);
+        let hashes = ["src/App.tsx", "src/Button.tsx"]
+            .iter()
+            .map(|file| {
+                let path = temp_dir.path().join(file);
+                let hash = hash_file(&path).unwrap();
+                (path, hash)
+            })
+            .collect();
+
+        (temp_dir, hashes)
+    }
+
+    fn insert(
+        cache: &mut AnalysisCache,
+        file: &Path,
+        dependency: &Path,
+        hashes: &HashMap<PathBuf, String>,
+    ) {
+        cache.insert(
+            file.to_path_buf(),
+            hashes[file].clone(),
+            vec![component(file, dependency)],
+            &[SourceDependency::File(dependency.to_path_buf())],
+            hashes,
+        );
+    }
+
+    #[test]
+    fn test_cache_version_mismatch() {
+        let (temp_dir, hashes) = mock_project();
+        let root = temp_dir.path();
+        let cache_dir = root.join(CACHE_DIR);
+        let app = root.join("src/App.tsx");
+        let resolver = ProjectResolver::new(None);
+
+        let mut cache = AnalysisCache::load(&cache_dir, "app", root);
+        insert(&mut cache, &app, &root.join("src/Button.tsx"), &hashes);
+        cache.save();
+
+        let written: CacheFile = serde_json::from_slice(&fs::read(&cache.path).unwrap()).unwrap();
+        for (version, fingerprint) in [
+            ("0.0.0", written.fingerprint.as_str()),
+            (env!("CARGO_PKG_VERSION"), "other"),
+        ] {
+            let cache_file = CacheFile {
+                version: version.to_string(),
+                fingerprint: fingerprint.to_string(),
+                entries: written.entries.clone(),
+            };
+            fs::write(&cache.path, serde_json::to_vec(&cache_file).unwrap()).unwrap();
+
+            let cache = AnalysisCache::load(&cache_dir, "app", root);
+            assert!(cache.entries.is_empty());
+            assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_none());
+        }
+    }
+
+    #[test]
+    fn test_cache_retain() {
+        let (temp_dir, hashes) = mock_project();
+        let root = temp_dir.path();
+        let cache_dir = root.join(CACHE_DIR);
+        let app = root.join("src/App.tsx");
+        let button = root.join("src/Button.tsx");
+        let resolver = ProjectResolver::new(None);
+
+        let mut cache = AnalysisCache::load(&cache_dir, "app", root);
+        insert(&mut cache, &app, &button, &hashes);
+        insert(&mut cache, &button, &app, &hashes);
+
+        // Button.tsx was deleted
+        cache.retain(std::slice::from_ref(&app));
+        cache.save();
+
+        let cache = AnalysisCache::load(&cache_dir, "app", root);
+        assert_eq!(cache.entries.keys().collect::<Vec<_>>(), vec![&app]);
+        assert!(cache
+            .get(&button, &hashes[&button], &hashes, &resolver)
+            .is_none());
+    }
+
+    #[test]
+    fn test_cache_dependency_changed() {
+        let (temp_dir, hashes) = mock_project();
+        let root = temp_dir.path();
+        let cache_dir = root.join(CACHE_DIR);
+        let app = root.join("src/App.tsx");
+        let resolver = ProjectResolver::new(None);
+
+        // a module outside the analyzed files, e.g. an installed package
+        let module = root.join("node_modules/ui/index.js");
+        fs::create_dir_all(module.parent().unwrap()).unwrap();
+        fs::write(&module, "export const Button = () => null;").unwrap();
+
+        let mut cache = AnalysisCache::load(&cache_dir, "app", root);
+        insert(&mut cache, &app, &module, &hashes);
+        assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_some());
+
+        fs::write(&module, "export const Button = () => 'changed';").unwrap();
+        assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_none());
+
+        // a deleted dependency invalidates the entry as well
+        fs::remove_file(&module).unwrap();
+        assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_none());
+    }
+
+    #[test]
+    fn test_cache_corrupt_file() {
+        let (temp_dir, hashes) = mock_project();
+        let root = temp_dir.path();
+        let cache_dir = root.join(CACHE_DIR);
+        let app = root.join("src/App.tsx");
+        let resolver = ProjectResolver::new(None);
+
+        let cache = AnalysisCache::load(&cache_dir, "app", root);
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(&cache.path, "{ not json").unwrap();
+
+        let mut cache = AnalysisCache::load(&cache_dir, "app", root);
+        assert!(cache.entries.is_empty());
+
+        // the corrupt cache is replaced on the next save
+        insert(&mut cache, &app, &root.join("src/Button.tsx"), &hashes);
+        cache.save();
+
+        let cache = AnalysisCache::load(&cache_dir, "app", root);
+        assert!(cache.get(&app, &hashes[&app], &hashes, &resolver).is_some());
+    }
+}
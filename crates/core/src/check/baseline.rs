@@ -0,0 +1,167 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{RuleKind, Violation};
+
+/// Identifies a violation independently of its line, so moving code doesn't invalidate a baseline
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct BaselineEntry {
+    rule: RuleKind,
+    file_path: String,
+    component: String,
+    subject: String,
+    /// How many violations of this kind are accepted
+    count: usize,
+}
+
+/// Violations that existed when the baseline was written.
+/// They are accepted by a check, so only new violations fail it.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    violations: Vec<BaselineEntry>,
+}
+
+type BaselineKey = (RuleKind, String, String, String);
+
+fn key(violation: &Violation) -> BaselineKey {
+    (
+        violation.rule,
+        violation.file_path.to_string_lossy().to_string(),
+        violation.component.clone(),
+        violation.subject.clone(),
+    )
+}
+
+impl Baseline {
+    /// Creates a baseline accepting all given violations
+    pub fn from_violations(violations: &[Violation]) -> Self {
+        let mut counts: BTreeMap<BaselineKey, usize> = BTreeMap::new();
+        for violation in violations {
+            *counts.entry(key(violation)).or_insert(0) += 1;
+        }
+
+        Self {
+            violations: counts
+                .into_iter()
+                .map(
+                    |((rule, file_path, component, subject), count)| BaselineEntry {
+                        rule,
+                        file_path,
+                        component,
+                        subject,
+                        count,
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    /// Reads a baseline file
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let content = fs::read(path)?;
+        serde_json::from_slice(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the baseline to a file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)
+    }
+
+    /// The number of violations the baseline accepts
+    pub fn len(&self) -> usize {
+        self.violations.iter().map(|entry| entry.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the violations accepted by the baseline and returns the new ones.
+    /// If a file has more violations of a kind than the baseline accepts, the last ones are new.
+    pub fn filter(&self, violations: Vec<Violation>) -> Vec<Violation> {
+        let mut accepted: BTreeMap<BaselineKey, usize> = self
+            .violations
+            .iter()
+            .map(|entry| {
+                (
+                    (
+                        entry.rule,
+                        entry.file_path.clone(),
+                        entry.component.clone(),
+                        entry.subject.clone(),
+                    ),
+                    entry.count,
+                )
+            })
+            .collect();
+
+        violations
+            .into_iter()
+            .filter(|violation| match accepted.get_mut(&key(violation)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        analyze::component::SourceLocation, check::Severity, util::test_utils::create_mock_project,
+    };
+
+    use super::*;
+
+    fn violation(line: usize, subject: &str) -> Violation {
+        Violation {
+            rule: RuleKind::Component,
+            severity: Severity::Error,
+            project: "app".to_string(),
+            file_path: PathBuf::from("app/src/Page.tsx"),
+            location: Some(SourceLocation { line, column: 1 }),
+            component: "Page".to_string(),
+            subject: subject.to_string(),
+            message: format!("`{}` is banned", subject),
+        }
+    }
+
+    #[test]
+    fn test_baseline() {
+        let temp_dir = create_mock_project(&vec![]);
+        let path = temp_dir.path().join("spinne-baseline.json");
+
+        Baseline::from_violations(&[violation(3, "OldButton"), violation(8, "OldButton")])
+            .save(&path)
+            .unwrap();
+        let baseline = Baseline::read(&path).unwrap();
+        assert_eq!(baseline.len(), 2);
+
+        // the accepted violations moved, one more of them and another one were added
+        let new_violations = baseline.filter(vec![
+            violation(4, "OldButton"),
+            violation(9, "OldButton"),
+            violation(12, "OldButton"),
+            violation(14, "OldCard"),
+        ]);
+
+        assert_eq!(
+            new_violations,
+            vec![violation(12, "OldButton"), violation(14, "OldCard")]
+        );
+    }
+
+    #[test]
+    fn test_invalid_baseline() {
+        let temp_dir = create_mock_project(&vec![("spinne-baseline.json", "[]")]);
+
+        assert!(Baseline::read(&temp_dir.path().join("spinne-baseline.json")).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+mod baseline;
+mod rules;
+
+use std::{fmt, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyze::component::SourceLocation;
+
+pub use baseline::Baseline;
+pub use rules::{ComponentRule, DependencyRule, IntrinsicElementRule, LimitRule, PropRule, Rules};
+
+/// How a violation affects the result of a check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Fails the check
+    #[default]
+    Error,
+    /// Is reported without failing the check, e.g. for deprecated components
+    Warning,
+}
+
+/// The rule a violation was found by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    Component,
+    Prop,
+    Dependency,
+    IntrinsicElement,
+    MaxDepth,
+    MaxFanOut,
+}
+
+impl RuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleKind::Component => "component",
+            RuleKind::Prop => "prop",
+            RuleKind::Dependency => "dependency",
+            RuleKind::IntrinsicElement => "intrinsic_element",
+            RuleKind::MaxDepth => "max_depth",
+            RuleKind::MaxFanOut => "max_fan_out",
+        }
+    }
+}
+
+/// A place where the component graph breaks a rule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Violation {
+    pub rule: RuleKind,
+    pub severity: Severity,
+    pub project: String,
+    /// The file of the component that breaks the rule, relative to the workspace root.
+    /// Components of installed packages have the package name as their file.
+    pub file_path: PathBuf,
+    pub location: Option<SourceLocation>,
+    /// The component that breaks the rule
+    pub component: String,
+    /// What the rule is about, e.g. the banned component or prop.
+    /// Together with the rule, file and component it identifies the violation in a baseline.
+    pub subject: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file_path.display())?;
+        if let Some(location) = self.location {
+            write!(f, ":{}:{}", location.line, location.column)?;
+        }
+
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        write!(f, ": {}[{}] {}", severity, self.rule.as_str(), self.message)
+    }
+}
@@ -0,0 +1,696 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use petgraph::{
+    algo::tarjan_scc,
+    graph::{DiGraph, NodeIndex},
+};
+use serde::Deserialize;
+
+use crate::{
+    analyze::component::SourceLocation,
+    graph::{ComponentEdge, ComponentInfo, ComponentRegistry},
+};
+
+use super::{RuleKind, Severity, Violation};
+
+/// Bans or deprecates a component, e.g. `{ "name": "OldButton", "replacement": "Button" }`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ComponentRule {
+    pub name: String,
+    /// Only applies to components from a project, package or path glob
+    pub from: Option<String>,
+    pub replacement: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// Bans or deprecates a prop of a component
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PropRule {
+    pub component: String,
+    pub prop: String,
+    /// Only applies to components from a project, package or path glob
+    pub from: Option<String>,
+    pub replacement: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// Forbids components matching `from` to render components matching `to`.
+/// Both match a project name, a package name or a glob of component paths relative to the
+/// workspace root like `apps/web/src/legacy/**`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DependencyRule {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// Requires a component instead of an intrinsic element,
+/// e.g. `{ "element": "button", "replacement": "Button from @acme/ui" }`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct IntrinsicElementRule {
+    pub element: String,
+    pub replacement: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+/// Limits a number of the component graph, either just the limit, e.g. `12`,
+/// or with a severity, e.g. `{ "limit": 12, "severity": "warning" }`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(from = "LimitConfig")]
+pub struct LimitRule {
+    pub limit: usize,
+    pub severity: Severity,
+}
+
+/// The forms a limit can be written in
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LimitConfig {
+    Limit(usize),
+    Rule {
+        limit: usize,
+        #[serde(default)]
+        severity: Severity,
+    },
+}
+
+impl From<LimitConfig> for LimitRule {
+    fn from(config: LimitConfig) -> Self {
+        match config {
+            LimitConfig::Limit(limit) => Self {
+                limit,
+                severity: Severity::default(),
+            },
+            LimitConfig::Rule { limit, severity } => Self { limit, severity },
+        }
+    }
+}
+
+/// The policy the component graph is checked against, read from `rules` in `spinne.json`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    pub components: Vec<ComponentRule>,
+    pub props: Vec<PropRule>,
+    pub forbidden_dependencies: Vec<DependencyRule>,
+    pub intrinsic_elements: Vec<IntrinsicElementRule>,
+    /// How many levels of components a component may render below itself
+    pub max_depth: Option<LimitRule>,
+    /// How many different components a component may render
+    pub max_fan_out: Option<LimitRule>,
+}
+
+/// A place where a dependency is rendered, with the props passed there
+struct RenderSite {
+    location: Option<SourceLocation>,
+    props: Vec<String>,
+}
+
+impl Rules {
+    /// Checks all components of the registry.
+    /// `project_paths` holds the directory of each project relative to the workspace root, by
+    /// project name. Violations are sorted by file and location.
+    pub fn check(
+        &self,
+        registry: &ComponentRegistry,
+        project_paths: &HashMap<String, PathBuf>,
+    ) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut components = registry.get_components();
+        components.sort_by(|a, b| a.node.id.cmp(&b.node.id));
+
+        for info in components {
+            let dependencies = registry.get_dependencies(&info.node.id);
+
+            for (id, edge) in &dependencies {
+                if let Some(dependency) = registry.get_component(id) {
+                    self.check_dependency(project_paths, info, dependency, edge, &mut violations);
+                }
+            }
+
+            self.check_intrinsic_elements(project_paths, info, &mut violations);
+
+            if let Some(max_fan_out) = self.max_fan_out {
+                if dependencies.len() > max_fan_out.limit {
+                    violations.push(violation(
+                        RuleKind::MaxFanOut,
+                        max_fan_out.severity,
+                        info,
+                        workspace_path(project_paths, info),
+                        info.node.location,
+                        String::new(),
+                        format!(
+                            "`{}` renders {} different components, the maximum is {}",
+                            info.node.name,
+                            dependencies.len(),
+                            max_fan_out.limit
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if let Some(max_depth) = self.max_depth {
+            // only the component at the top of a tree is reported
+            for (info, depth) in tree_depths(registry) {
+                if depth > max_depth.limit {
+                    violations.push(violation(
+                        RuleKind::MaxDepth,
+                        max_depth.severity,
+                        info,
+                        workspace_path(project_paths, info),
+                        info.node.location,
+                        String::new(),
+                        format!(
+                            "`{}` renders a tree {} components deep, the maximum is {}",
+                            info.node.name, depth, max_depth.limit
+                        ),
+                    ));
+                }
+            }
+        }
+
+        violations.sort_by(|a, b| {
+            let position = |violation: &Violation| {
+                violation
+                    .location
+                    .map(|location| (location.line, location.column))
+            };
+            (&a.file_path, position(a), a.rule, &a.subject).cmp(&(
+                &b.file_path,
+                position(b),
+                b.rule,
+                &b.subject,
+            ))
+        });
+
+        violations
+    }
+
+    /// Checks the places where `info` renders `dependency`
+    fn check_dependency(
+        &self,
+        project_paths: &HashMap<String, PathBuf>,
+        info: &ComponentInfo,
+        dependency: &ComponentInfo,
+        edge: &ComponentEdge,
+        violations: &mut Vec<Violation>,
+    ) {
+        let sites: Vec<RenderSite> = if edge.usages.is_empty() {
+            // dependencies without recorded usages are reported at the dependent
+            vec![RenderSite {
+                location: info.node.location,
+                props: edge.props.keys().cloned().collect(),
+            }]
+        } else {
+            edge.usages
+                .iter()
+                .map(|usage| RenderSite {
+                    location: Some(usage.location),
+                    props: usage.props.clone(),
+                })
+                .collect()
+        };
+        let name = &dependency.node.name;
+        let file_path = workspace_path(project_paths, info);
+        let dependency_path = workspace_path(project_paths, dependency);
+
+        for rule in &self.components {
+            if rule.name != *name
+                || !matches_from(rule.from.as_deref(), dependency, &dependency_path)
+            {
+                continue;
+            }
+
+            for site in &sites {
+                violations.push(violation(
+                    RuleKind::Component,
+                    rule.severity,
+                    info,
+                    file_path.clone(),
+                    site.location,
+                    name.clone(),
+                    format!(
+                        "`{}` is {}{}",
+                        name,
+                        describe(rule.severity),
+                        suggestion(rule.replacement.as_deref())
+                    ),
+                ));
+            }
+        }
+
+        for rule in &self.props {
+            if rule.component != *name
+                || !matches_from(rule.from.as_deref(), dependency, &dependency_path)
+            {
+                continue;
+            }
+
+            for site in sites.iter().filter(|site| site.props.contains(&rule.prop)) {
+                violations.push(violation(
+                    RuleKind::Prop,
+                    rule.severity,
+                    info,
+                    file_path.clone(),
+                    site.location,
+                    format!("{}.{}", name, rule.prop),
+                    format!(
+                        "prop `{}` of `{}` is {}{}",
+                        rule.prop,
+                        name,
+                        describe(rule.severity),
+                        suggestion(rule.replacement.as_deref())
+                    ),
+                ));
+            }
+        }
+
+        for rule in &self.forbidden_dependencies {
+            if !matches(&rule.from, info, &file_path)
+                || !matches(&rule.to, dependency, &dependency_path)
+            {
+                continue;
+            }
+
+            for site in &sites {
+                violations.push(violation(
+                    RuleKind::Dependency,
+                    rule.severity,
+                    info,
+                    file_path.clone(),
+                    site.location,
+                    name.clone(),
+                    format!(
+                        "`{}` must not render `{}` ({} must not depend on {})",
+                        info.node.name, name, rule.from, rule.to
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Checks the places where a component renders intrinsic elements
+    fn check_intrinsic_elements(
+        &self,
+        project_paths: &HashMap<String, PathBuf>,
+        info: &ComponentInfo,
+        violations: &mut Vec<Violation>,
+    ) {
+        for rule in &self.intrinsic_elements {
+            if !info.node.intrinsic_elements.contains_key(&rule.element) {
+                continue;
+            }
+
+            let locations: Vec<Option<SourceLocation>> =
+                match info.node.intrinsic_element_locations.get(&rule.element) {
+                    Some(locations) => locations.iter().copied().map(Some).collect(),
+                    // elements without recorded locations are reported at the component
+                    None => vec![info.node.location],
+                };
+
+            for location in locations {
+                violations.push(violation(
+                    RuleKind::IntrinsicElement,
+                    rule.severity,
+                    info,
+                    workspace_path(project_paths, info),
+                    location,
+                    rule.element.clone(),
+                    format!(
+                        "`<{}>` is {}{}",
+                        rule.element,
+                        describe(rule.severity),
+                        suggestion(rule.replacement.as_deref())
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn violation(
+    rule: RuleKind,
+    severity: Severity,
+    info: &ComponentInfo,
+    file_path: PathBuf,
+    location: Option<SourceLocation>,
+    subject: String,
+    message: String,
+) -> Violation {
+    Violation {
+        rule,
+        severity,
+        project: info.project.clone(),
+        file_path,
+        location,
+        component: info.node.name.clone(),
+        subject,
+        message,
+    }
+}
+
+/// The path of a component's file relative to the workspace root.
+/// Registered paths start with the project name, which is replaced with the directory of the
+/// project. Components of installed packages keep the package name.
+fn workspace_path(project_paths: &HashMap<String, PathBuf>, info: &ComponentInfo) -> PathBuf {
+    let file_path = &info.node.file_path;
+
+    match (
+        project_paths.get(&info.project),
+        file_path.strip_prefix(&info.project),
+    ) {
+        (Some(project_path), Ok(relative_path)) => project_path.join(relative_path),
+        _ => file_path.clone(),
+    }
+}
+
+/// Whether a pattern matches the project of a component, or the path of its file as a glob.
+/// The path of a component from an installed package is the package name.
+fn matches(pattern: &str, info: &ComponentInfo, file_path: &Path) -> bool {
+    pattern == info.project
+        || glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches_path(file_path))
+}
+
+fn matches_from(from: Option<&str>, info: &ComponentInfo, file_path: &Path) -> bool {
+    from.is_none_or(|from| matches(from, info, file_path))
+}
+
+fn describe(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "banned",
+        Severity::Warning => "deprecated",
+    }
+}
+
+fn suggestion(replacement: Option<&str>) -> String {
+    replacement
+        .map(|replacement| format!(", use {} instead", replacement))
+        .unwrap_or_default()
+}
+
+/// The components at the top of a tree with the number of component levels below them.
+/// Components that render each other are grouped first, so the result doesn't depend on where a
+/// cycle is entered: a cycle counts one level per component it passes through. A tree's top is a
+/// component no component outside its cycle renders. Of a cycle at the top, only the component
+/// with the smallest id is returned.
+fn tree_depths(registry: &ComponentRegistry) -> Vec<(&ComponentInfo, usize)> {
+    let mut components = registry.get_components();
+    components.sort_by(|a, b| a.node.id.cmp(&b.node.id));
+
+    let mut graph = DiGraph::<usize, ()>::new();
+    let nodes: HashMap<&str, NodeIndex> = components
+        .iter()
+        .enumerate()
+        .map(|(index, info)| (info.node.id.as_str(), graph.add_node(index)))
+        .collect();
+    for info in &components {
+        let mut dependencies: Vec<NodeIndex> = registry
+            .get_dependencies(&info.node.id)
+            .iter()
+            .filter_map(|(id, _)| nodes.get(id.as_str()).copied())
+            .collect();
+        dependencies.sort();
+        for dependency in dependencies {
+            graph.add_edge(nodes[info.node.id.as_str()], dependency, ());
+        }
+    }
+
+    // groups are ordered dependencies first, so the depths below a group are known
+    let groups = tarjan_scc(&graph);
+    let mut group_of = vec![0; graph.node_count()];
+    for (group, members) in groups.iter().enumerate() {
+        for member in members {
+            group_of[member.index()] = group;
+        }
+    }
+
+    let mut depths = vec![0; groups.len()];
+    let mut rendered = vec![false; groups.len()];
+    for (group, members) in groups.iter().enumerate() {
+        let mut below = 0;
+        let mut is_cycle = false;
+        for target in members.iter().flat_map(|&member| graph.neighbors(member)) {
+            let target_group = group_of[target.index()];
+            if target_group == group {
+                is_cycle = true;
+            } else {
+                rendered[target_group] = true;
+                below = below.max(1 + depths[target_group]);
+            }
+        }
+        depths[group] = below + if is_cycle { members.len() } else { 0 };
+    }
+
+    groups
+        .iter()
+        .enumerate()
+        .filter(|(group, _)| !rendered[*group])
+        .filter_map(|(group, members)| {
+            let top = members.iter().min()?;
+            Some((components[graph[*top]], depths[group]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use crate::{analyze::component::ComponentUsage, graph::ComponentNode};
+
+    use super::*;
+
+    fn usage(line: usize, props: &[&str]) -> ComponentUsage {
+        ComponentUsage {
+            location: SourceLocation { line, column: 5 },
+            props: props.iter().map(|prop| prop.to_string()).collect(),
+            spreads: Vec::new(),
+            has_unknown_spread: false,
+        }
+    }
+
+    fn add_component(
+        registry: &mut ComponentRegistry,
+        name: &str,
+        path: &str,
+        project: &str,
+    ) -> ComponentNode {
+        let mut node = ComponentNode::new(name.to_string(), PathBuf::from(path), BTreeMap::new());
+        node.location = Some(SourceLocation { line: 1, column: 1 });
+        registry.add_component(node.clone(), project.to_string());
+        node
+    }
+
+    fn render(
+        registry: &mut ComponentRegistry,
+        from: &ComponentNode,
+        to: &ComponentNode,
+        usage: ComponentUsage,
+    ) {
+        registry.add_dependency(&from.id, &to.id, None).unwrap();
+        let props = usage
+            .props
+            .iter()
+            .map(|prop| (prop.clone(), 1))
+            .collect::<BTreeMap<_, _>>();
+        registry.add_instance(&from.id, &to.id, usage, &props);
+    }
+
+    #[test]
+    fn test_component_and_prop_rules() {
+        let mut registry = ComponentRegistry::new();
+        let page = add_component(&mut registry, "Page", "app/src/Page.tsx", "app");
+        let old_button = add_component(&mut registry, "OldButton", "@acme/ui", "app");
+        let button = add_component(&mut registry, "Button", "ui/src/Button.tsx", "ui");
+        render(&mut registry, &page, &old_button, usage(4, &[]));
+        render(&mut registry, &page, &button, usage(5, &["color"]));
+        render(&mut registry, &page, &button, usage(6, &["variant"]));
+
+        let rules = Rules {
+            components: vec![
+                ComponentRule {
+                    name: "OldButton".to_string(),
+                    from: Some("@acme/ui".to_string()),
+                    replacement: Some("Button".to_string()),
+                    severity: Severity::Warning,
+                },
+                // components from other places are not affected
+                ComponentRule {
+                    name: "Button".to_string(),
+                    from: Some("legacy-ui".to_string()),
+                    replacement: None,
+                    severity: Severity::Error,
+                },
+            ],
+            props: vec![PropRule {
+                component: "Button".to_string(),
+                prop: "color".to_string(),
+                from: None,
+                replacement: Some("variant".to_string()),
+                severity: Severity::Error,
+            }],
+            ..Rules::default()
+        };
+        let violations = rules.check(&registry, &HashMap::new());
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].to_string(),
+            "app/src/Page.tsx:4:5: warning[component] `OldButton` is deprecated, use Button instead"
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            "app/src/Page.tsx:5:5: error[prop] prop `color` of `Button` is banned, use variant instead"
+        );
+        assert_eq!(violations[1].subject, "Button.color");
+    }
+
+    #[test]
+    fn test_dependency_and_intrinsic_element_rules() {
+        let mut registry = ComponentRegistry::new();
+        let mut page = ComponentNode::new(
+            "Page".to_string(),
+            PathBuf::from("app/src/legacy/Page.tsx"),
+            BTreeMap::new(),
+        );
+        page.intrinsic_elements = BTreeMap::from([("button".to_string(), 2)]);
+        page.intrinsic_element_locations = BTreeMap::from([(
+            "button".to_string(),
+            vec![
+                SourceLocation { line: 7, column: 9 },
+                SourceLocation { line: 9, column: 9 },
+            ],
+        )]);
+        registry.add_component(page.clone(), "app".to_string());
+        let card = add_component(&mut registry, "Card", "ui/src/Card.tsx", "ui");
+        let header = add_component(&mut registry, "Header", "app/src/Header.tsx", "app");
+        render(&mut registry, &page, &card, usage(3, &[]));
+        render(&mut registry, &page, &header, usage(4, &[]));
+
+        let rules = Rules {
+            forbidden_dependencies: vec![DependencyRule {
+                from: "apps/web/src/legacy/**".to_string(),
+                to: "ui".to_string(),
+                severity: Severity::Error,
+            }],
+            intrinsic_elements: vec![IntrinsicElementRule {
+                element: "button".to_string(),
+                replacement: Some("Button from @acme/ui".to_string()),
+                severity: Severity::Warning,
+            }],
+            ..Rules::default()
+        };
+        // projects are matched and reported by their directory in the workspace
+        let project_paths = HashMap::from([
+            ("app".to_string(), PathBuf::from("apps/web")),
+            ("ui".to_string(), PathBuf::from("packages/ui")),
+        ]);
+        let violations = rules.check(&registry, &project_paths);
+
+        assert_eq!(violations.len(), 3);
+        assert_eq!(
+            violations[0].to_string(),
+            "apps/web/src/legacy/Page.tsx:3:5: error[dependency] `Page` must not render `Card` (apps/web/src/legacy/** must not depend on ui)"
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            "apps/web/src/legacy/Page.tsx:7:9: warning[intrinsic_element] `<button>` is deprecated, use Button from @acme/ui instead"
+        );
+        assert_eq!(
+            violations[2].location,
+            Some(SourceLocation { line: 9, column: 9 })
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut registry = ComponentRegistry::new();
+        let app = add_component(&mut registry, "App", "app/src/App.tsx", "app");
+        let page = add_component(&mut registry, "Page", "app/src/Page.tsx", "app");
+        let card = add_component(&mut registry, "Card", "app/src/Card.tsx", "app");
+        let button = add_component(&mut registry, "Button", "app/src/Button.tsx", "app");
+        render(&mut registry, &app, &page, usage(2, &[]));
+        render(&mut registry, &page, &card, usage(2, &[]));
+        render(&mut registry, &page, &button, usage(3, &[]));
+        render(&mut registry, &card, &button, usage(2, &[]));
+        // a cycle doesn't stop the check
+        render(&mut registry, &button, &card, usage(2, &[]));
+
+        let rules = Rules {
+            max_depth: Some(LimitRule {
+                limit: 2,
+                severity: Severity::Error,
+            }),
+            max_fan_out: Some(LimitRule {
+                limit: 1,
+                severity: Severity::Warning,
+            }),
+            ..Rules::default()
+        };
+        let violations = rules.check(&registry, &HashMap::new());
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule, RuleKind::MaxDepth);
+        assert_eq!(violations[0].component, "App");
+        assert_eq!(
+            violations[0].message,
+            "`App` renders a tree 4 components deep, the maximum is 2"
+        );
+        assert_eq!(violations[0].severity, Severity::Error);
+        assert_eq!(violations[1].rule, RuleKind::MaxFanOut);
+        assert_eq!(violations[1].component, "Page");
+        assert_eq!(violations[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_max_depth_of_cycles() {
+        let check = |with_root: bool| {
+            let mut registry = ComponentRegistry::new();
+            let a = add_component(&mut registry, "A", "app/src/A.tsx", "app");
+            let b = add_component(&mut registry, "B", "app/src/B.tsx", "app");
+            let c = add_component(&mut registry, "C", "app/src/C.tsx", "app");
+            render(&mut registry, &a, &b, usage(2, &[]));
+            render(&mut registry, &b, &c, usage(2, &[]));
+            render(&mut registry, &c, &a, usage(2, &[]));
+            if with_root {
+                let root = add_component(&mut registry, "Root", "app/src/Root.tsx", "app");
+                render(&mut registry, &root, &b, usage(2, &[]));
+            }
+
+            let rules = Rules {
+                max_depth: Some(LimitRule {
+                    limit: 2,
+                    severity: Severity::Error,
+                }),
+                ..Rules::default()
+            };
+            rules
+                .check(&registry, &HashMap::new())
+                .into_iter()
+                .map(|violation| violation.message)
+                .collect::<Vec<_>>()
+        };
+
+        // a cycle nothing else renders is checked as well, reported at one of its components
+        let violations = check(false);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].ends_with("renders a tree 3 components deep, the maximum is 2"));
+
+        // the depth doesn't depend on where the cycle is entered
+        for _ in 0..10 {
+            assert_eq!(check(false), violations);
+            assert_eq!(
+                check(true),
+                vec!["`Root` renders a tree 4 components deep, the maximum is 2"]
+            );
+        }
+    }
+}
@@ -3,11 +3,15 @@ use std::{fs, path::PathBuf};
 use serde_json::Value;
 use spinne_logger::Logger;
 
+use crate::check::Rules;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ConfigValues {
     pub exclude: Option<Vec<String>>,
     pub include: Option<Vec<String>>,
     pub entry_points: Option<Vec<String>>,
+    /// The policy `spinne check` enforces
+    pub rules: Option<Rules>,
 }
 
 /// Represents the config file
@@ -34,31 +38,34 @@ impl Config {
                 let include_value = value.get("include");
                 let entry_points_value = value.get("entry_points");
 
-                let exclude = match exclude_value {
-                    Some(value) => Some(Self::get_array_of_strings(value)),
-                    None => None,
-                };
+                let exclude = exclude_value.map(Self::get_array_of_strings);
+
+                let include = include_value.map(Self::get_array_of_strings);
 
-                let include = match include_value {
-                    Some(value) => Some(Self::get_array_of_strings(value)),
-                    None => None,
-                };
+                let entry_points = entry_points_value.map(Self::get_array_of_strings);
 
-                let entry_points = match entry_points_value {
-                    Some(value) => Some(Self::get_array_of_strings(value)),
-                    None => None,
-                };
+                let rules = value.get("rules").and_then(|rules| {
+                    match serde_json::from_value::<Rules>(rules.clone()) {
+                        Ok(rules) => Some(rules),
+                        Err(err) => {
+                            Logger::error("Failed to parse rules in config file");
+                            Logger::error(&err.to_string());
+                            None
+                        }
+                    }
+                });
 
                 Some(ConfigValues {
                     exclude,
                     include,
                     entry_points,
+                    rules,
                 })
             }
             Err(err) => {
                 Logger::error("Failed to parse config file");
                 Logger::error(&err.to_string());
-                return None;
+                None
             }
         }
     }
@@ -76,7 +83,10 @@ impl Config {
 
 #[cfg(test)]
 mod tests {
-    use crate::util::test_utils::create_mock_project;
+    use crate::{
+        check::{LimitRule, Severity},
+        util::test_utils::create_mock_project,
+    };
 
     use super::*;
 
@@ -93,7 +103,8 @@ mod tests {
             Some(ConfigValues {
                 exclude: Some(vec!["test.tsx".to_string()]),
                 include: Some(vec!["test.tsx".to_string()]),
-                entry_points: Some(vec!["src/index.tsx".to_string()])
+                entry_points: Some(vec!["src/index.tsx".to_string()]),
+                rules: None
             })
         );
     }
@@ -124,7 +135,8 @@ mod tests {
             Some(ConfigValues {
                 exclude: Some(vec!["test.tsx".to_string()]),
                 include: None,
-                entry_points: None
+                entry_points: None,
+                rules: None
             })
         );
     }
@@ -139,7 +151,8 @@ mod tests {
             Some(ConfigValues {
                 exclude: None,
                 include: Some(vec!["test.tsx".to_string()]),
-                entry_points: None
+                entry_points: None,
+                rules: None
             })
         );
     }
@@ -154,7 +167,8 @@ mod tests {
             Some(ConfigValues {
                 exclude: None,
                 include: Some(vec!["test.tsx".to_string()]),
-                entry_points: None
+                entry_points: None,
+                rules: None
             })
         );
     }
@@ -175,8 +189,59 @@ mod tests {
                 entry_points: Some(vec![
                     "src/index.tsx".to_string(),
                     "src/components/index.ts".to_string()
-                ])
+                ]),
+                rules: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_config_with_rules() {
+        let temp_dir = create_mock_project(&vec![(
+            "spinne.json",
+            r#"{
+                "rules": {
+                    "components": [{ "name": "OldButton", "replacement": "Button", "severity": "warning" }],
+                    "forbidden_dependencies": [{ "from": "app", "to": "legacy-ui" }],
+                    "max_fan_out": 10,
+                    "max_depth": { "limit": 8, "severity": "warning" }
+                }
+            }"#,
+        )]);
+        let rules = Config::read(temp_dir.path().join("spinne.json"))
+            .unwrap()
+            .rules
+            .unwrap();
+
+        assert_eq!(rules.components[0].name, "OldButton");
+        assert_eq!(rules.components[0].severity, Severity::Warning);
+        assert_eq!(rules.forbidden_dependencies[0].severity, Severity::Error);
+        assert_eq!(
+            rules.max_fan_out,
+            Some(LimitRule {
+                limit: 10,
+                severity: Severity::Error
             })
         );
+        assert_eq!(
+            rules.max_depth,
+            Some(LimitRule {
+                limit: 8,
+                severity: Severity::Warning
+            })
+        );
+        assert!(rules.props.is_empty());
+    }
+
+    #[test]
+    fn test_config_with_invalid_rules() {
+        let temp_dir = create_mock_project(&vec![(
+            "spinne.json",
+            r#"{"include": ["test.tsx"], "rules": {"components": [{"replacement": "Button"}]}}"#,
+        )]);
+        let config = Config::read(temp_dir.path().join("spinne.json")).unwrap();
+
+        assert_eq!(config.include, Some(vec!["test.tsx".to_string()]));
+        assert_eq!(config.rules, None);
     }
 }
@@ -0,0 +1,226 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use crate::analyze::component::ElementSource;
+
+use super::report::{
+    AdoptionReport, DirectoryAdoptionReport, ElementCountsReport, ElementPercentagesReport,
+};
+
+/// How many rendered elements come from each source
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementCounts {
+    pub intrinsic: usize,
+    pub local: usize,
+    pub workspace: usize,
+    pub third_party: usize,
+}
+
+impl ElementCounts {
+    /// Counts rendered elements of a source
+    pub fn add(&mut self, source: ElementSource, count: usize) {
+        match source {
+            ElementSource::Intrinsic => self.intrinsic += count,
+            ElementSource::Local => self.local += count,
+            ElementSource::Workspace => self.workspace += count,
+            ElementSource::ThirdParty => self.third_party += count,
+        }
+    }
+
+    /// Adds the counts of another set of elements
+    pub fn merge(&mut self, other: &ElementCounts) {
+        self.intrinsic += other.intrinsic;
+        self.local += other.local;
+        self.workspace += other.workspace;
+        self.third_party += other.third_party;
+    }
+
+    pub fn total(&self) -> usize {
+        self.intrinsic + self.local + self.workspace + self.third_party
+    }
+
+    /// Elements that come from a component library, i.e. a workspace or an installed package
+    pub fn library(&self) -> usize {
+        self.workspace + self.third_party
+    }
+
+    /// Converts the counts into the report format
+    pub fn to_report(&self) -> ElementCountsReport {
+        ElementCountsReport {
+            intrinsic: self.intrinsic,
+            local: self.local,
+            workspace: self.workspace,
+            third_party: self.third_party,
+            total: self.total(),
+        }
+    }
+
+    /// The share of each source of all elements in percent, rounded to two decimals
+    pub fn percentages(&self) -> ElementPercentagesReport {
+        let total = self.total();
+        let percentage = |count: usize| {
+            if total == 0 {
+                0.0
+            } else {
+                (count as f64 * 10000.0 / total as f64).round() / 100.0
+            }
+        };
+
+        ElementPercentagesReport {
+            intrinsic: percentage(self.intrinsic),
+            local: percentage(self.local),
+            workspace: percentage(self.workspace),
+            third_party: percentage(self.third_party),
+            library: percentage(self.library()),
+        }
+    }
+
+    /// Reads counts back from a report
+    pub fn from_report(report: &ElementCountsReport) -> Self {
+        Self {
+            intrinsic: report.intrinsic,
+            local: report.local,
+            workspace: report.workspace,
+            third_party: report.third_party,
+        }
+    }
+}
+
+/// Tracks which share of the elements rendered in a project come from component libraries
+/// compared to native elements and local components
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Adoption {
+    /// Element counts by the directory of the rendering component
+    directories: BTreeMap<PathBuf, ElementCounts>,
+    /// Usage counts of each intrinsic element, e.g. `div`
+    intrinsic_elements: HashMap<String, usize>,
+}
+
+impl Adoption {
+    /// Counts elements rendered by a component in the given directory
+    pub fn add(&mut self, directory: &Path, source: ElementSource, count: usize) {
+        self.directories
+            .entry(directory.to_path_buf())
+            .or_default()
+            .add(source, count);
+    }
+
+    /// Counts the intrinsic elements rendered by a component in the given directory
+    pub fn add_intrinsic_elements(&mut self, directory: &Path, elements: &BTreeMap<String, usize>) {
+        for (element, count) in elements {
+            self.add(directory, ElementSource::Intrinsic, *count);
+            *self.intrinsic_elements.entry(element.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// Adds the counts of another project's adoption
+    pub fn merge(&mut self, other: &Adoption) {
+        for (directory, counts) in &other.directories {
+            self.directories
+                .entry(directory.clone())
+                .or_default()
+                .merge(counts);
+        }
+        for (element, count) in &other.intrinsic_elements {
+            *self.intrinsic_elements.entry(element.clone()).or_insert(0) += count;
+        }
+    }
+
+    /// The counts of all elements of the project
+    pub fn total(&self) -> ElementCounts {
+        let mut total = ElementCounts::default();
+        for counts in self.directories.values() {
+            total.merge(counts);
+        }
+
+        total
+    }
+
+    /// The counts of the elements rendered in a directory
+    pub fn get_directory(&self, directory: &Path) -> Option<&ElementCounts> {
+        self.directories.get(directory)
+    }
+
+    /// Converts the adoption of the project and its directories into the report format
+    pub fn to_report(&self) -> AdoptionReport {
+        let total = self.total();
+
+        AdoptionReport {
+            elements: total.to_report(),
+            percentages: total.percentages(),
+            intrinsic_elements: self.intrinsic_elements.clone().into_iter().collect(),
+            directories: self
+                .directories
+                .iter()
+                .map(|(directory, counts)| DirectoryAdoptionReport {
+                    path: directory.clone(),
+                    elements: counts.to_report(),
+                    percentages: counts.percentages(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads the adoption of a project back from a report
+    pub fn from_report(report: &AdoptionReport) -> Self {
+        Self {
+            directories: report
+                .directories
+                .iter()
+                .map(|directory| {
+                    (
+                        directory.path.clone(),
+                        ElementCounts::from_report(&directory.elements),
+                    )
+                })
+                .collect(),
+            intrinsic_elements: report.intrinsic_elements.clone().into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adoption() {
+        let mut adoption = Adoption::default();
+        let pages = Path::new("app/src/pages");
+        let components = Path::new("app/src/components");
+
+        adoption.add_intrinsic_elements(pages, &BTreeMap::from([("div".to_string(), 2)]));
+        adoption.add(pages, ElementSource::ThirdParty, 1);
+        adoption.add(pages, ElementSource::Workspace, 1);
+        adoption.add_intrinsic_elements(components, &BTreeMap::from([("button".to_string(), 1)]));
+        adoption.add(components, ElementSource::Local, 2);
+
+        let mut other = Adoption::default();
+        other.add_intrinsic_elements(pages, &BTreeMap::from([("div".to_string(), 1)]));
+        adoption.merge(&other);
+
+        assert_eq!(
+            adoption.total(),
+            ElementCounts {
+                intrinsic: 4,
+                local: 2,
+                workspace: 1,
+                third_party: 1,
+            }
+        );
+        assert_eq!(adoption.get_directory(pages).unwrap().library(), 2);
+
+        let report = adoption.to_report();
+        assert_eq!(Adoption::from_report(&report), adoption);
+
+        let serialized = serde_json::to_value(report).unwrap();
+        assert_eq!(serialized["elements"]["total"], 8);
+        assert_eq!(serialized["percentages"]["library"], 25.0);
+        assert_eq!(serialized["percentages"]["intrinsic"], 50.0);
+        assert_eq!(serialized["intrinsic_elements"]["div"], 3);
+        assert_eq!(serialized["directories"][1]["path"], "app/src/pages");
+        assert_eq!(serialized["directories"][1]["percentages"]["library"], 40.0);
+    }
+}
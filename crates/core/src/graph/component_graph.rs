@@ -1,9 +1,11 @@
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::report::{ComponentReport, EdgeReport, GraphReport, ProjectReport, Report};
 use std::{
-    collections::{HashMap, HashSet},
-    path::PathBuf,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
 };
-use sha2::{Digest, Sha256};
 
 /// Represents a component with a unique identifier.
 #[derive(Debug, Clone)]
@@ -17,7 +19,7 @@ pub struct Component {
     /// The path to the component's file relative to the project root.
     pub path_relative_to_root: PathBuf,
     /// The properties of the component.
-    pub props: HashMap<String, usize>,
+    pub props: BTreeMap<String, usize>,
     /// The project the component belongs to.
     pub project: String,
 }
@@ -36,13 +38,13 @@ impl Component {
             name,
             path,
             path_relative_to_root,
-            props: HashMap::new(),
+            props: BTreeMap::new(),
             project,
         }
     }
 
     /// Computes a hash string from a component's name and path
-    fn compute_hash(name: &str, path: &PathBuf) -> String {
+    fn compute_hash(name: &str, path: &Path) -> String {
         let mut hasher = Sha256::new();
         hasher.update(name.as_bytes());
         hasher.update(path.to_string_lossy().as_bytes());
@@ -51,7 +53,6 @@ impl Component {
     }
 }
 
-
 /// A simple graph where nodes are components identified by a unique value,
 /// and edges represent the relationship "uses."
 #[derive(Debug, Clone)]
@@ -62,6 +63,12 @@ pub struct ComponentGraph {
     edges: HashMap<String, HashSet<String>>,
 }
 
+impl Default for ComponentGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ComponentGraph {
     /// Creates a new, empty Graph.
     pub fn new() -> Self {
@@ -112,8 +119,7 @@ impl ComponentGraph {
         for dep in deps {
             let dep_id = dep.id.clone();
             self.nodes.insert(dep_id.clone(), dep);
-            self
-                .edges
+            self.edges
                 .entry(component_id.clone())
                 .or_default()
                 .insert(dep_id);
@@ -129,7 +135,7 @@ impl ComponentGraph {
 
     /// Retrieves a component by its name and path.
     /// This is a convenience method for finding a component by its name and path.
-    pub fn find_component(&self, name: &str, path: &PathBuf) -> Option<&Component> {
+    pub fn find_component(&self, name: &str, path: &Path) -> Option<&Component> {
         let id = Component::compute_hash(name, path);
         self.nodes.get(&id)
     }
@@ -143,7 +149,7 @@ impl ComponentGraph {
     pub fn has_edge(&self, from_id: &str, to_id: &str) -> bool {
         self.edges
             .get(from_id)
-            .map_or(false, |neighbors| neighbors.contains(to_id))
+            .is_some_and(|neighbors| neighbors.contains(to_id))
     }
 
     /// Adds a property to a component.
@@ -163,36 +169,52 @@ impl ComponentGraph {
         self.edges.len()
     }
 
-    /// Converts the component graph into a serializable format for JSON output
-    pub fn to_serializable(&self) -> Value {
-        let mut components = Vec::new();
-        let mut edges = Vec::new();
+    /// Converts the component graph into the report format, grouping components by project.
+    /// Edges to a component of another project carry its project as `project_context`.
+    pub fn to_report(&self) -> Report {
+        let mut projects: HashMap<String, GraphReport> = HashMap::new();
 
-        // Convert nodes to serializable format
         for component in self.nodes.values() {
-            components.push(serde_json::json!({
-                "id": component.id,
-                "name": component.name,
-                "path": component.path_relative_to_root,
-                "props": component.props,
-                "project": component.project,
-            }));
-        }
-
-        // Convert edges to serializable format
-        for (from_id, to_ids) in &self.edges {
-            for to_id in to_ids {
-                edges.push(serde_json::json!({
-                    "from": from_id,
-                    "to": to_id,
-                }));
+            let graph = projects.entry(component.project.clone()).or_default();
+
+            graph.components.push(ComponentReport {
+                id: component.id.clone(),
+                name: component.name.clone(),
+                path: component.path_relative_to_root.clone(),
+                props: component.props.clone(),
+                ..ComponentReport::default()
+            });
+
+            for to_id in self.edges.get(&component.id).into_iter().flatten() {
+                let project_context = self
+                    .nodes
+                    .get(to_id)
+                    .map(|target| &target.project)
+                    .filter(|project| **project != component.project)
+                    .cloned();
+
+                graph.edges.push(EdgeReport {
+                    from: component.id.clone(),
+                    to: to_id.clone(),
+                    project_context,
+                    count: 1,
+                    ..EdgeReport::default()
+                });
             }
         }
 
-        serde_json::json!({
-            "components": components,
-            "edges": edges,
-        })
+        Report::new(
+            projects
+                .into_iter()
+                .map(|(name, graph)| ProjectReport { name, graph })
+                .collect(),
+        )
+    }
+
+    /// Converts the component graph into a serializable format for JSON output.
+    /// The format is the same as the one of `ComponentRegistry::to_serializable`.
+    pub fn to_serializable(&self) -> Value {
+        self.to_report().to_value()
     }
 }
 
@@ -264,6 +286,46 @@ mod tests {
         assert!(neighbors.contains(&id2));
     }
 
+    #[test]
+    fn test_to_report() {
+        let mut graph = ComponentGraph::new();
+        let app = Component::new(
+            "App".to_string(),
+            PathBuf::from("/app/src/App.tsx"),
+            PathBuf::from("app/src/App.tsx"),
+            "app".to_string(),
+        );
+        let button = Component::new(
+            "Button".to_string(),
+            PathBuf::from("/ui/src/Button.tsx"),
+            PathBuf::from("ui/src/Button.tsx"),
+            "ui".to_string(),
+        );
+        let (app_id, button_id) = (app.id.clone(), button.id.clone());
+        graph.add_component(app);
+        graph.add_component(button);
+        graph.add_edge(&app_id, &button_id);
+
+        let report = graph.to_report();
+        let app_project = report
+            .projects
+            .iter()
+            .find(|project| project.name == "app")
+            .unwrap();
+
+        assert_eq!(report.projects.len(), 2);
+        assert_eq!(
+            app_project.graph.components[0].path,
+            PathBuf::from("app/src/App.tsx")
+        );
+        assert_eq!(app_project.graph.edges[0].to, button_id);
+        assert_eq!(
+            app_project.graph.edges[0].project_context,
+            Some("ui".to_string())
+        );
+        assert_eq!(graph.to_serializable()["schema_version"], 1);
+    }
+
     #[test]
     fn test_invalid_edges() {
         let mut graph = ComponentGraph::new();
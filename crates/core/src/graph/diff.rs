@@ -0,0 +1,704 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Write,
+    path::PathBuf,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::specialized_graph::{ComponentInfo, ComponentRegistry};
+
+/// A component referenced in a diff. The id is stable across runs as long as the name and path
+/// of the component stay the same.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ComponentRef {
+    pub project: String,
+    pub path: PathBuf,
+    pub name: String,
+    pub id: String,
+}
+
+impl ComponentRef {
+    fn new(info: &ComponentInfo) -> Self {
+        Self {
+            project: info.project.clone(),
+            path: info.node.file_path.clone(),
+            name: info.node.name.clone(),
+            id: info.node.id.clone(),
+        }
+    }
+}
+
+/// A dependency between two components that was added or removed
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct EdgeChange {
+    pub from: ComponentRef,
+    pub to: ComponentRef,
+    pub project_context: Option<String>,
+}
+
+/// A prop whose usage count changed
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct PropChange {
+    pub component: ComponentRef,
+    pub prop: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+/// A dependency of one project on another, through at least one edge
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct ProjectDependency {
+    pub from: String,
+    pub to: String,
+}
+
+/// The changes between two registries, e.g. the reports of the main branch and a pull request.
+/// Components and edges are matched by their ids. All changes are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RegistryDiff {
+    pub added_components: Vec<ComponentRef>,
+    pub removed_components: Vec<ComponentRef>,
+    pub added_edges: Vec<EdgeChange>,
+    pub removed_edges: Vec<EdgeChange>,
+    pub prop_changes: Vec<PropChange>,
+    pub added_project_dependencies: Vec<ProjectDependency>,
+    pub removed_project_dependencies: Vec<ProjectDependency>,
+}
+
+/// The number of changes of each kind in a diff
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffSummary {
+    pub added_components: usize,
+    pub removed_components: usize,
+    pub added_edges: usize,
+    pub removed_edges: usize,
+    pub prop_changes: usize,
+    pub added_project_dependencies: usize,
+    pub removed_project_dependencies: usize,
+}
+
+/// A diff as written by `spinne diff --format json`: the summary followed by the changes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffReport<'a> {
+    pub summary: DiffSummary,
+    #[serde(flatten)]
+    pub diff: &'a RegistryDiff,
+}
+
+/// The edges of a registry by the ids of their components
+fn edges(registry: &ComponentRegistry) -> BTreeMap<(String, String), EdgeChange> {
+    let mut edges = BTreeMap::new();
+
+    for info in registry.get_components() {
+        for (id, edge) in registry.get_dependencies(&info.node.id) {
+            if let Some(dependency) = registry.get_component(&id) {
+                edges.insert(
+                    (info.node.id.clone(), id),
+                    EdgeChange {
+                        from: ComponentRef::new(info),
+                        to: ComponentRef::new(dependency),
+                        project_context: edge.project_context.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    edges
+}
+
+/// The projects that depend on another project.
+/// Components of installed packages belong to the project importing them, so only the
+/// `project_context` of an edge points to another project for them.
+fn project_dependencies(
+    edges: &BTreeMap<(String, String), EdgeChange>,
+) -> HashSet<ProjectDependency> {
+    edges
+        .values()
+        .filter_map(|edge| {
+            let to = edge
+                .project_context
+                .clone()
+                .unwrap_or_else(|| edge.to.project.clone());
+
+            (to != edge.from.project).then(|| ProjectDependency {
+                from: edge.from.project.clone(),
+                to,
+            })
+        })
+        .collect()
+}
+
+impl RegistryDiff {
+    /// Compares an older registry with a newer one
+    pub fn new(old: &ComponentRegistry, new: &ComponentRegistry) -> Self {
+        let mut diff = Self::default();
+
+        for info in new.get_components() {
+            match old.get_component(&info.node.id) {
+                None => diff.added_components.push(ComponentRef::new(info)),
+                Some(old_info) => {
+                    let props: BTreeSet<&String> = info
+                        .node
+                        .props
+                        .keys()
+                        .chain(old_info.node.props.keys())
+                        .collect();
+
+                    for prop in props {
+                        let before = old_info.node.props.get(prop).copied().unwrap_or(0);
+                        let after = info.node.props.get(prop).copied().unwrap_or(0);
+                        if before != after {
+                            diff.prop_changes.push(PropChange {
+                                component: ComponentRef::new(info),
+                                prop: prop.clone(),
+                                before,
+                                after,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        for info in old.get_components() {
+            if new.get_component(&info.node.id).is_none() {
+                diff.removed_components.push(ComponentRef::new(info));
+            }
+        }
+
+        let old_edges = edges(old);
+        let new_edges = edges(new);
+        diff.added_edges = new_edges
+            .iter()
+            .filter(|(key, _)| !old_edges.contains_key(*key))
+            .map(|(_, edge)| edge.clone())
+            .collect();
+        diff.removed_edges = old_edges
+            .iter()
+            .filter(|(key, _)| !new_edges.contains_key(*key))
+            .map(|(_, edge)| edge.clone())
+            .collect();
+
+        let old_projects = project_dependencies(&old_edges);
+        let new_projects = project_dependencies(&new_edges);
+        diff.added_project_dependencies = new_projects.difference(&old_projects).cloned().collect();
+        diff.removed_project_dependencies =
+            old_projects.difference(&new_projects).cloned().collect();
+
+        diff.added_components.sort();
+        diff.removed_components.sort();
+        diff.added_edges.sort();
+        diff.removed_edges.sort();
+        diff.prop_changes.sort();
+        diff.added_project_dependencies.sort();
+        diff.removed_project_dependencies.sort();
+
+        diff
+    }
+
+    /// Whether both registries contain the same components, edges and prop counts
+    pub fn is_empty(&self) -> bool {
+        self.added_components.is_empty()
+            && self.removed_components.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.prop_changes.is_empty()
+            && self.added_project_dependencies.is_empty()
+            && self.removed_project_dependencies.is_empty()
+    }
+
+    /// Counts the changes of each kind
+    pub fn summary(&self) -> DiffSummary {
+        DiffSummary {
+            added_components: self.added_components.len(),
+            removed_components: self.removed_components.len(),
+            added_edges: self.added_edges.len(),
+            removed_edges: self.removed_edges.len(),
+            prop_changes: self.prop_changes.len(),
+            added_project_dependencies: self.added_project_dependencies.len(),
+            removed_project_dependencies: self.removed_project_dependencies.len(),
+        }
+    }
+
+    /// Converts the diff into the format of the JSON output
+    pub fn to_report(&self) -> DiffReport<'_> {
+        DiffReport {
+            summary: self.summary(),
+            diff: self,
+        }
+    }
+
+    /// Converts the diff into a serializable format for JSON output
+    pub fn to_serializable(&self) -> Value {
+        serde_json::to_value(self.to_report()).unwrap_or_default()
+    }
+
+    /// Renders the diff as Markdown, e.g. for a comment on a pull request
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("## Component changes\n\n");
+
+        if self.is_empty() {
+            markdown.push_str("No component changes.\n");
+            return markdown;
+        }
+
+        let _ = writeln!(
+            markdown,
+            "{} components added, {} removed, {} dependencies added, {} removed, {} prop usage changes\n",
+            self.added_components.len(),
+            self.removed_components.len(),
+            self.added_edges.len(),
+            self.removed_edges.len(),
+            self.prop_changes.len()
+        );
+
+        for (title, components) in [
+            ("Added components", &self.added_components),
+            ("Removed components", &self.removed_components),
+        ] {
+            if components.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(markdown, "### {}\n", title);
+            markdown.push_str("| Component | Project | Path |\n| --- | --- | --- |\n");
+            for component in components {
+                let _ = writeln!(
+                    markdown,
+                    "| `{}` | {} | `{}` |",
+                    component.name,
+                    component.project,
+                    component.path.display()
+                );
+            }
+            markdown.push('\n');
+        }
+
+        for (title, edges) in [
+            ("Added dependencies", &self.added_edges),
+            ("Removed dependencies", &self.removed_edges),
+        ] {
+            if edges.is_empty() {
+                continue;
+            }
+
+            let _ = writeln!(markdown, "### {}\n", title);
+            markdown.push_str("| From | To |\n| --- | --- |\n");
+            for edge in edges {
+                let _ = writeln!(
+                    markdown,
+                    "| `{}` ({}) | `{}` ({}) |",
+                    edge.from.name, edge.from.project, edge.to.name, edge.to.project
+                );
+            }
+            markdown.push('\n');
+        }
+
+        if !self.prop_changes.is_empty() {
+            markdown.push_str("### Prop usage changes\n\n");
+            markdown.push_str(
+                "| Component | Prop | Before | After | Change |\n| --- | --- | --- | --- | --- |\n",
+            );
+            for change in &self.prop_changes {
+                let _ = writeln!(
+                    markdown,
+                    "| `{}` ({}) | `{}` | {} | {} | {:+} |",
+                    change.component.name,
+                    change.component.project,
+                    change.prop,
+                    change.before,
+                    change.after,
+                    change.after as i64 - change.before as i64
+                );
+            }
+            markdown.push('\n');
+        }
+
+        if !self.added_project_dependencies.is_empty()
+            || !self.removed_project_dependencies.is_empty()
+        {
+            markdown.push_str("### Cross-project dependencies\n\n");
+            markdown.push_str("| From | To | Change |\n| --- | --- | --- |\n");
+            for (change, dependencies) in [
+                ("added", &self.added_project_dependencies),
+                ("removed", &self.removed_project_dependencies),
+            ] {
+                for dependency in dependencies {
+                    let _ = writeln!(
+                        markdown,
+                        "| {} | {} | {} |",
+                        dependency.from, dependency.to, change
+                    );
+                }
+            }
+            markdown.push('\n');
+        }
+
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::ComponentNode;
+
+    use super::*;
+
+    fn component(name: &str, path: &str, props: &[(&str, usize)]) -> ComponentNode {
+        ComponentNode::new(
+            name.to_string(),
+            PathBuf::from(path),
+            props
+                .iter()
+                .map(|(prop, count)| (prop.to_string(), *count))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn test_diff() {
+        let app = component("App", "app/src/App.tsx", &[]);
+        let page = component("Page", "app/src/Page.tsx", &[]);
+        let button = component("Button", "ui/src/Button.tsx", &[("variant", 2)]);
+        let old_button = component("OldButton", "app/src/OldButton.tsx", &[]);
+
+        let mut old = ComponentRegistry::new();
+        old.add_component(app.clone(), "app".to_string());
+        old.add_component(page.clone(), "app".to_string());
+        old.add_component(old_button.clone(), "app".to_string());
+        old.add_component(button.clone(), "ui".to_string());
+        old.add_dependency(&app.id, &page.id, None).unwrap();
+        old.add_dependency(&page.id, &old_button.id, None).unwrap();
+
+        let mut new = ComponentRegistry::new();
+        let card = component("Card", "app/src/Card.tsx", &[]);
+        new.add_component(app.clone(), "app".to_string());
+        new.add_component(page.clone(), "app".to_string());
+        new.add_component(card.clone(), "app".to_string());
+        new.add_component(
+            component(
+                "Button",
+                "ui/src/Button.tsx",
+                &[("variant", 3), ("size", 1)],
+            ),
+            "ui".to_string(),
+        );
+        new.add_dependency(&app.id, &page.id, None).unwrap();
+        new.add_dependency(&page.id, &card.id, None).unwrap();
+        new.add_dependency(&page.id, &button.id, Some("ui".to_string()))
+            .unwrap();
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.added_components,
+            vec![ComponentRef::new(new.get_component(&card.id).unwrap())]
+        );
+        assert_eq!(diff.removed_components[0].name, "OldButton");
+        let added_edges: Vec<(&str, &str)> = diff
+            .added_edges
+            .iter()
+            .map(|edge| (edge.from.name.as_str(), edge.to.name.as_str()))
+            .collect();
+        assert_eq!(added_edges, vec![("Page", "Card"), ("Page", "Button")]);
+        assert_eq!(diff.removed_edges[0].to.name, "OldButton");
+        assert_eq!(
+            diff.prop_changes
+                .iter()
+                .map(|change| (change.prop.as_str(), change.before, change.after))
+                .collect::<Vec<_>>(),
+            vec![("size", 0, 1), ("variant", 2, 3)]
+        );
+        assert_eq!(
+            diff.added_project_dependencies,
+            vec![ProjectDependency {
+                from: "app".to_string(),
+                to: "ui".to_string()
+            }]
+        );
+        assert!(diff.removed_project_dependencies.is_empty());
+
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("| `Card` | app | `app/src/Card.tsx` |"));
+        assert!(markdown.contains("| `Page` (app) | `Button` (ui) |"));
+        assert!(markdown.contains("| `Button` (ui) | `variant` | 2 | 3 | +1 |"));
+        assert!(markdown.contains("| app | ui | added |"));
+
+        assert_eq!(diff.to_serializable()["summary"]["added_edges"], 2);
+        assert!(new.diff(&new).is_empty());
+        assert_eq!(
+            new.diff(&new).to_markdown(),
+            "## Component changes\n\nNo component changes.\n"
+        );
+    }
+
+    /// Registers components with their dependencies, `(from, to)` by index
+    fn registry(
+        components: &[(&ComponentNode, &str)],
+        edges: &[(usize, usize, Option<&str>)],
+    ) -> ComponentRegistry {
+        let mut registry = ComponentRegistry::new();
+        for (component, project) in components {
+            registry.add_component((*component).clone(), project.to_string());
+        }
+        for (from, to, project_context) in edges {
+            registry
+                .add_dependency(
+                    &components[*from].0.id,
+                    &components[*to].0.id,
+                    project_context.map(str::to_string),
+                )
+                .unwrap();
+        }
+
+        registry
+    }
+
+    #[test]
+    fn test_diff_removed_components() {
+        let app = component("App", "app/src/App.tsx", &[]);
+        let modal = component("Modal", "app/src/Modal.tsx", &[("open", 1)]);
+
+        let old = registry(&[(&app, "app"), (&modal, "app")], &[(0, 1, None)]);
+        let new = registry(&[(&app, "app")], &[]);
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.removed_components,
+            vec![ComponentRef {
+                project: "app".to_string(),
+                path: PathBuf::from("app/src/Modal.tsx"),
+                name: "Modal".to_string(),
+                id: modal.id.clone(),
+            }]
+        );
+        assert!(diff.added_components.is_empty());
+        // the props of a removed component are not reported as changes
+        assert!(diff.prop_changes.is_empty());
+        assert_eq!(diff.removed_edges.len(), 1);
+        assert_eq!(diff.removed_edges[0].to, diff.removed_components[0]);
+
+        // the reverse diff adds the component again
+        let reverse = new.diff(&old);
+        assert_eq!(reverse.added_components, diff.removed_components);
+        assert_eq!(reverse.added_edges, diff.removed_edges);
+    }
+
+    #[test]
+    fn test_diff_edges() {
+        let app = component("App", "app/src/App.tsx", &[]);
+        let header = component("Header", "app/src/Header.tsx", &[]);
+        let button = component("Button", "ui/src/Button.tsx", &[]);
+        let link = component("Link", "ui/src/Link.tsx", &[]);
+        let components = [
+            (&app, "app"),
+            (&header, "app"),
+            (&button, "ui"),
+            (&link, "ui"),
+        ];
+
+        let old = registry(
+            &components,
+            &[(0, 1, None), (1, 2, Some("ui")), (2, 3, None)],
+        );
+        let new = registry(&components, &[(0, 1, None), (0, 2, Some("ui"))]);
+        let diff = old.diff(&new);
+
+        let names = |edges: &[EdgeChange]| -> Vec<(String, String, Option<String>)> {
+            edges
+                .iter()
+                .map(|edge| {
+                    (
+                        edge.from.name.clone(),
+                        edge.to.name.clone(),
+                        edge.project_context.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(
+            names(&diff.added_edges),
+            vec![(
+                "App".to_string(),
+                "Button".to_string(),
+                Some("ui".to_string())
+            )]
+        );
+        assert_eq!(
+            names(&diff.removed_edges),
+            vec![
+                (
+                    "Header".to_string(),
+                    "Button".to_string(),
+                    Some("ui".to_string())
+                ),
+                ("Button".to_string(), "Link".to_string(), None),
+            ]
+        );
+        assert!(diff.added_components.is_empty());
+        assert!(diff.removed_components.is_empty());
+        // app still depends on ui through App
+        assert!(diff.added_project_dependencies.is_empty());
+        assert!(diff.removed_project_dependencies.is_empty());
+
+        let new = registry(&components, &[(0, 1, None)]);
+        assert_eq!(
+            old.diff(&new).removed_project_dependencies,
+            vec![ProjectDependency {
+                from: "app".to_string(),
+                to: "ui".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_prop_changes() {
+        let old_button = component(
+            "Button",
+            "ui/src/Button.tsx",
+            &[("variant", 2), ("size", 1)],
+        );
+        let new_button = component(
+            "Button",
+            "ui/src/Button.tsx",
+            &[("variant", 2), ("disabled", 3)],
+        );
+        let card = component("Card", "ui/src/Card.tsx", &[("title", 1)]);
+
+        let old = registry(&[(&old_button, "ui")], &[]);
+        let new = registry(&[(&new_button, "ui"), (&card, "ui")], &[]);
+        let diff = old.diff(&new);
+
+        // unchanged props and the props of added components are not listed
+        assert_eq!(
+            diff.prop_changes,
+            vec![
+                PropChange {
+                    component: ComponentRef::new(new.get_component(&new_button.id).unwrap()),
+                    prop: "disabled".to_string(),
+                    before: 0,
+                    after: 3,
+                },
+                PropChange {
+                    component: ComponentRef::new(new.get_component(&new_button.id).unwrap()),
+                    prop: "size".to_string(),
+                    before: 1,
+                    after: 0,
+                },
+            ]
+        );
+        assert!(diff
+            .to_markdown()
+            .contains("| `Button` (ui) | `size` | 1 | 0 | -1 |"));
+    }
+
+    #[test]
+    fn test_diff_markdown() {
+        let app = component("App", "app/src/App.tsx", &[]);
+        let button = component("Button", "ui/src/Button.tsx", &[("variant", 1)]);
+        let legacy = component("LegacyButton", "app/src/LegacyButton.tsx", &[]);
+
+        let old = registry(
+            &[(&app, "app"), (&button, "ui"), (&legacy, "app")],
+            &[(0, 2, None)],
+        );
+        let new = registry(
+            &[
+                (&app, "app"),
+                (
+                    &component("Button", "ui/src/Button.tsx", &[("variant", 3)]),
+                    "ui",
+                ),
+            ],
+            &[(0, 1, Some("ui"))],
+        );
+
+        assert_eq!(
+            old.diff(&new).to_markdown(),
+            "\
+## Component changes
+
+0 components added, 1 removed, 1 dependencies added, 1 removed, 1 prop usage changes
+
+### Removed components
+
+| Component | Project | Path |
+| --- | --- | --- |
+| `LegacyButton` | app | `app/src/LegacyButton.tsx` |
+
+### Added dependencies
+
+| From | To |
+| --- | --- |
+| `App` (app) | `Button` (ui) |
+
+### Removed dependencies
+
+| From | To |
+| --- | --- |
+| `App` (app) | `LegacyButton` (app) |
+
+### Prop usage changes
+
+| Component | Prop | Before | After | Change |
+| --- | --- | --- | --- | --- |
+| `Button` (ui) | `variant` | 1 | 3 | +2 |
+
+### Cross-project dependencies
+
+| From | To | Change |
+| --- | --- | --- |
+| app | ui | added |
+
+"
+        );
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let app = component("App", "app/src/App.tsx", &[]);
+        let card = component("Card", "app/src/Card.tsx", &[]);
+
+        let old = registry(&[(&app, "app")], &[]);
+        let new = registry(&[(&app, "app"), (&card, "app")], &[(0, 1, None)]);
+        let card_ref = serde_json::json!({
+            "project": "app",
+            "path": "app/src/Card.tsx",
+            "name": "Card",
+            "id": card.id,
+        });
+
+        assert_eq!(
+            old.diff(&new).to_serializable(),
+            serde_json::json!({
+                "summary": {
+                    "added_components": 1,
+                    "removed_components": 0,
+                    "added_edges": 1,
+                    "removed_edges": 0,
+                    "prop_changes": 0,
+                    "added_project_dependencies": 0,
+                    "removed_project_dependencies": 0
+                },
+                "added_components": [card_ref],
+                "removed_components": [],
+                "added_edges": [{
+                    "from": {
+                        "project": "app",
+                        "path": "app/src/App.tsx",
+                        "name": "App",
+                        "id": app.id,
+                    },
+                    "to": card_ref,
+                    "project_context": null
+                }],
+                "removed_edges": [],
+                "prop_changes": [],
+                "added_project_dependencies": [],
+                "removed_project_dependencies": []
+            })
+        );
+    }
+}
@@ -0,0 +1,628 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::Write,
+};
+
+use super::specialized_graph::{ComponentEdge, ComponentInfo, ComponentRegistry};
+
+/// Which part of the registry is exported
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Only export the components rendered by the component with this name or id
+    pub root: Option<String>,
+    /// Only export components up to this many dependencies away from the root. Without a root,
+    /// the depth is counted from the components that are not rendered by any other component.
+    pub depth: Option<usize>,
+}
+
+/// A dependency between two exported components, by their index in `GraphExport::components`
+#[derive(Debug, Clone)]
+pub struct ExportEdge<'a> {
+    pub from: usize,
+    pub to: usize,
+    pub edge: &'a ComponentEdge,
+    /// Whether the `project_context` of the edge is another project than the one of `from`
+    pub cross_project: bool,
+}
+
+/// A part of the registry prepared for output in graph formats like Graphviz DOT, Mermaid,
+/// GraphML or GEXF.
+/// Components are sorted by project, path and name, so the output is stable between runs.
+#[derive(Debug, Clone)]
+pub struct GraphExport<'a> {
+    pub components: Vec<&'a ComponentInfo>,
+    /// How often each component is rendered in the whole registry, by index
+    pub usages: Vec<usize>,
+    pub edges: Vec<ExportEdge<'a>>,
+}
+
+/// Attributes of the exported components besides their name, with their GraphML type
+const NODE_ATTRIBUTES: [(&str, &str); 6] = [
+    ("project", "string"),
+    ("path", "string"),
+    ("kind", "string"),
+    ("props", "int"),
+    ("declared_props", "int"),
+    ("usages", "int"),
+];
+
+/// Attributes of the exported edges, with their GraphML type
+const EDGE_ATTRIBUTES: [(&str, &str); 2] = [("project_context", "string"), ("multiplicity", "int")];
+
+impl<'a> GraphExport<'a> {
+    /// Selects the components and edges to export.
+    /// Fails if the root of the options doesn't match any component.
+    pub fn new(registry: &'a ComponentRegistry, options: &ExportOptions) -> Result<Self, String> {
+        let mut components = match &options.root {
+            Some(root) => {
+                let roots: Vec<&ComponentInfo> = registry
+                    .get_components()
+                    .into_iter()
+                    .filter(|info| info.node.name == *root || info.node.id == *root)
+                    .collect();
+                if roots.is_empty() {
+                    return Err(format!("No component named {}", root));
+                }
+
+                select(registry, roots, options.depth)
+            }
+            None => match options.depth {
+                Some(depth) => {
+                    let roots = registry
+                        .get_components()
+                        .into_iter()
+                        .filter(|info| registry.get_dependents(&info.node.id).is_empty())
+                        .collect();
+
+                    select(registry, roots, Some(depth))
+                }
+                None => registry.get_components(),
+            },
+        };
+        components.sort_by(|a, b| {
+            (&a.project, &a.node.file_path, &a.node.name, &a.node.id).cmp(&(
+                &b.project,
+                &b.node.file_path,
+                &b.node.name,
+                &b.node.id,
+            ))
+        });
+
+        let indices: HashMap<&str, usize> = components
+            .iter()
+            .enumerate()
+            .map(|(index, info)| (info.node.id.as_str(), index))
+            .collect();
+
+        let mut edges = Vec::new();
+        for (from, info) in components.iter().enumerate() {
+            for (id, edge) in registry.get_dependencies(&info.node.id) {
+                if let Some(&to) = indices.get(id.as_str()) {
+                    let cross_project = edge
+                        .project_context
+                        .as_ref()
+                        .unwrap_or(&components[to].project)
+                        != &info.project;
+
+                    edges.push(ExportEdge {
+                        from,
+                        to,
+                        edge,
+                        cross_project,
+                    });
+                }
+            }
+        }
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+
+        let usages = components
+            .iter()
+            .map(|info| {
+                registry
+                    .get_dependents(&info.node.id)
+                    .iter()
+                    .flat_map(|id| registry.get_dependencies(id))
+                    .filter(|(id, _)| *id == info.node.id)
+                    .map(|(_, edge)| edge.count)
+                    .sum()
+            })
+            .collect();
+
+        Ok(Self {
+            components,
+            usages,
+            edges,
+        })
+    }
+
+    /// The indices of the exported components of each project, sorted by project name
+    pub fn projects(&self) -> BTreeMap<&'a str, Vec<usize>> {
+        let mut projects: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (index, info) in self.components.iter().enumerate() {
+            projects.entry(&info.project).or_default().push(index);
+        }
+        projects
+    }
+
+    /// Renders the graph in the Graphviz DOT language. Every project is drawn as a cluster and
+    /// dependencies on other projects as dashed edges.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph components {\n");
+        dot.push_str("  rankdir=LR;\n");
+        dot.push_str("  node [shape=box, style=rounded];\n");
+
+        for (cluster, (project, indices)) in self.projects().into_iter().enumerate() {
+            let _ = writeln!(dot, "\n  subgraph cluster_{} {{", cluster);
+            let _ = writeln!(dot, "    label=\"{}\";", escape_dot(project));
+            for index in indices {
+                let info = self.components[index];
+                let _ = writeln!(
+                    dot,
+                    "    c{} [label=\"{}\", tooltip=\"{}\"];",
+                    index,
+                    escape_dot(&info.node.name),
+                    escape_dot(&info.node.file_path.to_string_lossy())
+                );
+            }
+            dot.push_str("  }\n");
+        }
+
+        if !self.edges.is_empty() {
+            dot.push('\n');
+        }
+        for edge in &self.edges {
+            let mut attributes = Vec::new();
+            if edge.cross_project {
+                attributes.push("style=dashed".to_string());
+                attributes.push("color=\"#1f77b4\"".to_string());
+            }
+            if edge.edge.count > 1 {
+                attributes.push(format!("label=\"{}\"", edge.edge.count));
+            }
+
+            let _ = write!(dot, "  c{} -> c{}", edge.from, edge.to);
+            if !attributes.is_empty() {
+                let _ = write!(dot, " [{}]", attributes.join(", "));
+            }
+            dot.push_str(";\n");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as a Mermaid flowchart. Every project is drawn as a subgraph and
+    /// dependencies on other projects as dotted edges.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+
+        for (subgraph, (project, indices)) in self.projects().into_iter().enumerate() {
+            let _ = writeln!(
+                mermaid,
+                "  subgraph p{}[\"{}\"]",
+                subgraph,
+                escape_mermaid(project)
+            );
+            for index in indices {
+                let _ = writeln!(
+                    mermaid,
+                    "    c{}[\"{}\"]",
+                    index,
+                    escape_mermaid(&self.components[index].node.name)
+                );
+            }
+            mermaid.push_str("  end\n");
+        }
+
+        for edge in &self.edges {
+            let arrow = if edge.cross_project { "-.->" } else { "-->" };
+            let label = if edge.edge.count > 1 {
+                format!("|{}|", edge.edge.count)
+            } else {
+                String::new()
+            };
+
+            let _ = writeln!(mermaid, "  c{} {}{} c{}", edge.from, arrow, label, edge.to);
+        }
+
+        mermaid
+    }
+
+    /// The values of `NODE_ATTRIBUTES` for a component
+    fn node_values(&self, index: usize) -> [String; 6] {
+        let info = self.components[index];
+        [
+            info.project.clone(),
+            info.node.file_path.to_string_lossy().to_string(),
+            info.node.kind.as_str().to_string(),
+            info.node.props.len().to_string(),
+            info.node.declared_props.len().to_string(),
+            self.usages[index].to_string(),
+        ]
+    }
+
+    /// The values of `EDGE_ATTRIBUTES` for an edge
+    fn edge_values(edge: &ExportEdge) -> [String; 2] {
+        [
+            edge.edge.project_context.clone().unwrap_or_default(),
+            edge.edge.count.to_string(),
+        ]
+    }
+
+    /// Renders the graph as GraphML, e.g. for yEd or NetworkX. Components have the attributes
+    /// name, project, path, props (number of props passed), declared_props, usages (how often
+    /// the component is rendered) and kind. Edges have the attributes project_context and
+    /// multiplicity (how often the dependency is rendered).
+    pub fn to_graphml(&self) -> String {
+        let mut graphml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        graphml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+
+        let _ = writeln!(
+            graphml,
+            "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>"
+        );
+        for (target, attributes) in [("node", &NODE_ATTRIBUTES[..]), ("edge", &EDGE_ATTRIBUTES)] {
+            for (name, kind) in attributes {
+                let _ = writeln!(
+                    graphml,
+                    "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                    name, target, name, kind
+                );
+            }
+        }
+
+        graphml.push_str("  <graph id=\"components\" edgedefault=\"directed\">\n");
+        for (index, info) in self.components.iter().enumerate() {
+            let _ = writeln!(graphml, "    <node id=\"c{}\">", index);
+            let _ = writeln!(
+                graphml,
+                "      <data key=\"name\">{}</data>",
+                escape_xml(&info.node.name)
+            );
+            for ((name, _), value) in NODE_ATTRIBUTES.iter().zip(self.node_values(index)) {
+                let _ = writeln!(
+                    graphml,
+                    "      <data key=\"{}\">{}</data>",
+                    name,
+                    escape_xml(&value)
+                );
+            }
+            graphml.push_str("    </node>\n");
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                graphml,
+                "    <edge id=\"e{}\" source=\"c{}\" target=\"c{}\">",
+                index, edge.from, edge.to
+            );
+            for ((name, _), value) in EDGE_ATTRIBUTES.iter().zip(Self::edge_values(edge)) {
+                let _ = writeln!(
+                    graphml,
+                    "      <data key=\"{}\">{}</data>",
+                    name,
+                    escape_xml(&value)
+                );
+            }
+            graphml.push_str("    </edge>\n");
+        }
+        graphml.push_str("  </graph>\n</graphml>\n");
+
+        graphml
+    }
+
+    /// Renders the graph as GEXF 1.3, e.g. for Gephi. Components are labeled with their name and
+    /// have the same attributes as in GraphML. The weight of an edge is its multiplicity.
+    pub fn to_gexf(&self) -> String {
+        let mut gexf = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        gexf.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+        gexf.push_str("  <graph defaultedgetype=\"directed\" mode=\"static\">\n");
+
+        for (class, attributes) in [("node", &NODE_ATTRIBUTES[..]), ("edge", &EDGE_ATTRIBUTES)] {
+            let _ = writeln!(gexf, "    <attributes class=\"{}\">", class);
+            for (id, (name, kind)) in attributes.iter().enumerate() {
+                let kind = if *kind == "int" { "integer" } else { kind };
+                let _ = writeln!(
+                    gexf,
+                    "      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>",
+                    id, name, kind
+                );
+            }
+            gexf.push_str("    </attributes>\n");
+        }
+
+        gexf.push_str("    <nodes>\n");
+        for (index, info) in self.components.iter().enumerate() {
+            let _ = writeln!(
+                gexf,
+                "      <node id=\"c{}\" label=\"{}\">",
+                index,
+                escape_xml(&info.node.name)
+            );
+            write_attvalues(&mut gexf, &self.node_values(index));
+            gexf.push_str("      </node>\n");
+        }
+        gexf.push_str("    </nodes>\n");
+
+        gexf.push_str("    <edges>\n");
+        for (index, edge) in self.edges.iter().enumerate() {
+            let _ = write!(
+                gexf,
+                "      <edge id=\"e{}\" source=\"c{}\" target=\"c{}\"",
+                index, edge.from, edge.to
+            );
+            if edge.edge.count > 0 {
+                let _ = write!(gexf, " weight=\"{}\"", edge.edge.count);
+            }
+            gexf.push_str(">\n");
+            write_attvalues(&mut gexf, &Self::edge_values(edge));
+            gexf.push_str("      </edge>\n");
+        }
+        gexf.push_str("    </edges>\n");
+
+        gexf.push_str("  </graph>\n</gexf>\n");
+        gexf
+    }
+}
+
+/// Writes the attribute values of a GEXF node or edge, by the index of their attribute
+fn write_attvalues(gexf: &mut String, values: &[String]) {
+    gexf.push_str("        <attvalues>\n");
+    for (id, value) in values.iter().enumerate() {
+        let _ = writeln!(
+            gexf,
+            "          <attvalue for=\"{}\" value=\"{}\"/>",
+            id,
+            escape_xml(value)
+        );
+    }
+    gexf.push_str("        </attvalues>\n");
+}
+
+/// Selects the components reachable from the roots, up to the given depth
+fn select<'a>(
+    registry: &'a ComponentRegistry,
+    roots: Vec<&'a ComponentInfo>,
+    depth: Option<usize>,
+) -> Vec<&'a ComponentInfo> {
+    let mut selected: HashMap<&str, &ComponentInfo> = HashMap::new();
+    let mut queue: VecDeque<(&ComponentInfo, usize)> = VecDeque::new();
+
+    for root in roots {
+        if selected.insert(&root.node.id, root).is_none() {
+            queue.push_back((root, 0));
+        }
+    }
+
+    while let Some((info, distance)) = queue.pop_front() {
+        if depth.is_some_and(|depth| distance >= depth) {
+            continue;
+        }
+
+        for (id, _) in registry.get_dependencies(&info.node.id) {
+            if let Some(dependency) = registry.get_component(&id) {
+                if selected.insert(&dependency.node.id, dependency).is_none() {
+                    queue.push_back((dependency, distance + 1));
+                }
+            }
+        }
+    }
+
+    selected.into_values().collect()
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::component::{ComponentUsage, SourceLocation};
+    use crate::graph::ComponentNode;
+    use std::path::PathBuf;
+
+    /// App renders Page, Page renders Card twice and Button from another project
+    fn registry() -> ComponentRegistry {
+        let mut registry = ComponentRegistry::new();
+        let node = |name: &str, path: &str| {
+            ComponentNode::new(name.to_string(), PathBuf::from(path), BTreeMap::new())
+        };
+        let app = node("App", "app/src/App.tsx");
+        let page = node("Page", "app/src/Page.tsx");
+        let card = node("Card", "app/src/Card.tsx");
+        let button = node("Button", "ui/src/Button.tsx");
+
+        registry.add_component(app.clone(), "app".to_string());
+        registry.add_component(page.clone(), "app".to_string());
+        registry.add_component(card.clone(), "app".to_string());
+        registry.add_component(button.clone(), "ui".to_string());
+
+        registry
+            .add_dependency(&app.id, &page.id, Some("app".to_string()))
+            .unwrap();
+        registry.add_dependency(&page.id, &card.id, None).unwrap();
+        for line in [3, 4] {
+            let usage = ComponentUsage {
+                location: SourceLocation { line, column: 5 },
+                props: Vec::new(),
+                spreads: Vec::new(),
+                has_unknown_spread: false,
+            };
+            registry.add_instance(&page.id, &card.id, usage, &BTreeMap::new());
+        }
+        registry
+            .add_dependency(&page.id, &button.id, Some("ui".to_string()))
+            .unwrap();
+
+        registry
+    }
+
+    fn names(export: &GraphExport) -> Vec<String> {
+        export
+            .components
+            .iter()
+            .map(|info| info.node.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let registry = registry();
+        let dot = GraphExport::new(&registry, &ExportOptions::default())
+            .unwrap()
+            .to_dot();
+
+        assert_eq!(
+            dot,
+            r##"digraph components {
+  rankdir=LR;
+  node [shape=box, style=rounded];
+
+  subgraph cluster_0 {
+    label="app";
+    c0 [label="App", tooltip="app/src/App.tsx"];
+    c1 [label="Card", tooltip="app/src/Card.tsx"];
+    c2 [label="Page", tooltip="app/src/Page.tsx"];
+  }
+
+  subgraph cluster_1 {
+    label="ui";
+    c3 [label="Button", tooltip="ui/src/Button.tsx"];
+  }
+
+  c0 -> c2;
+  c2 -> c1 [label="2"];
+  c2 -> c3 [style=dashed, color="#1f77b4"];
+}
+"##
+        );
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let registry = registry();
+        let mermaid = GraphExport::new(&registry, &ExportOptions::default())
+            .unwrap()
+            .to_mermaid();
+
+        assert_eq!(
+            mermaid,
+            r#"flowchart LR
+  subgraph p0["app"]
+    c0["App"]
+    c1["Card"]
+    c2["Page"]
+  end
+  subgraph p1["ui"]
+    c3["Button"]
+  end
+  c0 --> c2
+  c2 -->|2| c1
+  c2 -.-> c3
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_graphml() {
+        let registry = registry();
+        let graphml = GraphExport::new(&registry, &ExportOptions::default())
+            .unwrap()
+            .to_graphml();
+
+        assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml"));
+        assert!(graphml.contains(
+            "<key id=\"multiplicity\" for=\"edge\" attr.name=\"multiplicity\" attr.type=\"int\"/>"
+        ));
+        assert_eq!(graphml.matches("<node ").count(), 4);
+        assert!(graphml.contains(
+            r#"    <node id="c1">
+      <data key="name">Card</data>
+      <data key="project">app</data>
+      <data key="path">app/src/Card.tsx</data>
+      <data key="kind">function</data>
+      <data key="props">0</data>
+      <data key="declared_props">0</data>
+      <data key="usages">2</data>
+    </node>"#
+        ));
+        assert!(graphml.contains(
+            r#"    <edge id="e1" source="c2" target="c1">
+      <data key="project_context"></data>
+      <data key="multiplicity">2</data>
+    </edge>"#
+        ));
+        assert!(graphml.contains(r#"<data key="project_context">ui</data>"#));
+        assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+    }
+
+    #[test]
+    fn test_to_gexf() {
+        let registry = registry();
+        let gexf = GraphExport::new(&registry, &ExportOptions::default())
+            .unwrap()
+            .to_gexf();
+
+        assert!(gexf.contains(r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#));
+        assert!(gexf.contains(r#"<attribute id="5" title="usages" type="integer"/>"#));
+        assert!(gexf.contains(r#"<attribute id="1" title="multiplicity" type="integer"/>"#));
+        assert!(gexf.contains(
+            r#"      <node id="c1" label="Card">
+        <attvalues>
+          <attvalue for="0" value="app"/>
+          <attvalue for="1" value="app/src/Card.tsx"/>
+          <attvalue for="2" value="function"/>
+          <attvalue for="3" value="0"/>
+          <attvalue for="4" value="0"/>
+          <attvalue for="5" value="2"/>
+        </attvalues>
+      </node>"#
+        ));
+        assert!(gexf.contains(r#"<edge id="e1" source="c2" target="c1" weight="2">"#));
+        assert!(gexf.contains(r#"<edge id="e0" source="c0" target="c2">"#));
+        assert_eq!(gexf.matches("<edge ").count(), 3);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<Tabs.Panel title="a & b">"#),
+            "&lt;Tabs.Panel title=&quot;a &amp; b&quot;&gt;"
+        );
+    }
+
+    #[test]
+    fn test_subgraph() {
+        let registry = registry();
+        let options = |root: Option<&str>, depth: Option<usize>| ExportOptions {
+            root: root.map(str::to_string),
+            depth,
+        };
+
+        let export = GraphExport::new(&registry, &options(Some("Page"), None)).unwrap();
+        assert_eq!(names(&export), vec!["Card", "Page", "Button"]);
+        assert_eq!(export.edges.len(), 2);
+
+        let export = GraphExport::new(&registry, &options(Some("App"), Some(1))).unwrap();
+        assert_eq!(names(&export), vec!["App", "Page"]);
+        assert_eq!(export.edges.len(), 1);
+
+        let export = GraphExport::new(&registry, &options(None, Some(0))).unwrap();
+        assert_eq!(names(&export), vec!["App"]);
+        assert!(export.edges.is_empty());
+
+        assert!(GraphExport::new(&registry, &options(Some("Missing"), None)).is_err());
+    }
+}
@@ -1,7 +1,19 @@
+mod adoption;
 mod component_graph;
+mod diff;
+mod export;
+mod report;
 mod specialized_graph;
 
 pub use component_graph::Component;
 pub use component_graph::ComponentGraph;
-pub use specialized_graph::ComponentNode;
+pub use diff::{
+    ComponentRef, DiffReport, DiffSummary, EdgeChange, ProjectDependency, PropChange, RegistryDiff,
+};
+pub use export::{ExportEdge, ExportOptions, GraphExport};
+pub use report::{
+    AdoptionReport, ComponentReport, DirectoryAdoptionReport, EdgeReport, ElementCountsReport,
+    ElementPercentagesReport, GraphReport, ProjectReport, Report, REPORT_SCHEMA_VERSION,
+};
 pub use specialized_graph::ComponentRegistry;
+pub use specialized_graph::{ComponentEdge, ComponentInfo, ComponentNode};
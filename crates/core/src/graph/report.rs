@@ -0,0 +1,329 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::analyze::component::{
+    ComponentKind, ComponentUsage, ComponentWrapper, DeclaredProp, PropValueStats, SourceLocation,
+};
+
+/// The version of the report format. It is increased whenever a change to the format could break
+/// tools reading reports, e.g. a field is removed, renamed or changes its type.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// The result of an analysis, as written to `spinne-report.json`.
+/// Projects, components and edges are serialized in a stable order, so two runs on the same
+/// code produce the same JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Report {
+    /// The version of the report format. It is increased with changes that could break tools
+    /// reading reports.
+    pub schema_version: u32,
+    /// Projects sorted by name
+    #[serde(serialize_with = "serialize_sorted")]
+    pub projects: Vec<ProjectReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProjectReport {
+    /// The name of the project, from the name field of its package.json
+    pub name: String,
+    pub graph: GraphReport,
+}
+
+/// The components of a project and the dependencies they render
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GraphReport {
+    /// Components sorted by path and name
+    pub components: Vec<ComponentReport>,
+    /// Dependencies of the components of the project, including those on other projects,
+    /// sorted by the ids of the components they connect
+    pub edges: Vec<EdgeReport>,
+    /// Ids of the components that can't be reached from an entry point of the project, sorted
+    pub dead_code_candidates: Vec<String>,
+    pub adoption: AdoptionReport,
+}
+
+/// A component of a project.
+/// Fields that older reports don't contain are empty when a report is read.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ComponentReport {
+    /// Hash of the name and path of the component, stable between runs
+    pub id: String,
+    pub name: String,
+    /// The file of the component prefixed with its project name, or the package name for
+    /// components of installed packages
+    pub path: PathBuf,
+    /// How often each prop is passed to the component
+    pub props: BTreeMap<String, usize>,
+    /// The values passed to each prop
+    pub prop_values: BTreeMap<String, PropValueStats>,
+    /// Props declared in the component's signature
+    pub declared_props: Vec<DeclaredProp>,
+    pub kind: ComponentKind,
+    /// Wrappers like `memo` applied to the component, from outermost to innermost
+    pub wrappers: Vec<ComponentWrapper>,
+    /// Where the component is defined, if its definition was analyzed
+    pub location: Option<SourceLocation>,
+    /// Whether the component can be reached from an entry point, `null` without entry points
+    pub reachable: Option<bool>,
+    /// Whether the component is exported from an entry point of its project
+    pub public_api: bool,
+    /// The installed version of the package the component is imported from
+    pub package_version: Option<String>,
+    /// Intrinsic elements like `div` rendered by the component, with usage counts
+    pub intrinsic_elements: BTreeMap<String, usize>,
+}
+
+/// A component rendering another component
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct EdgeReport {
+    /// Id of the rendering component
+    pub from: String,
+    /// Id of the rendered component
+    pub to: String,
+    /// The project of the rendered component if it is another project
+    pub project_context: Option<String>,
+    /// How often the component is rendered
+    pub count: usize,
+    /// How often each prop is passed at the places the component is rendered
+    pub props: BTreeMap<String, usize>,
+    /// The places the component is rendered at, sorted by line and column
+    pub usages: Vec<ComponentUsage>,
+}
+
+/// How many rendered elements come from each source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ElementCountsReport {
+    /// Native HTML and SVG elements like `<div>`
+    pub intrinsic: usize,
+    /// Components of the same project
+    pub local: usize,
+    /// Components of another project in the workspace
+    pub workspace: usize,
+    /// Components of installed packages
+    pub third_party: usize,
+    pub total: usize,
+}
+
+/// The share of each source in percent, rounded to two decimals
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ElementPercentagesReport {
+    pub intrinsic: f64,
+    pub local: f64,
+    pub workspace: f64,
+    pub third_party: f64,
+    /// Workspace and third-party components together
+    pub library: f64,
+}
+
+/// Where the elements rendered in a directory come from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DirectoryAdoptionReport {
+    pub path: PathBuf,
+    pub elements: ElementCountsReport,
+    pub percentages: ElementPercentagesReport,
+}
+
+/// Where the elements rendered in a project come from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct AdoptionReport {
+    pub elements: ElementCountsReport,
+    pub percentages: ElementPercentagesReport,
+    /// Usage counts of each intrinsic element
+    pub intrinsic_elements: BTreeMap<String, usize>,
+    /// Directories sorted by path
+    pub directories: Vec<DirectoryAdoptionReport>,
+}
+
+impl GraphReport {
+    /// Sorts components, edges and directories, so the graph is serialized in a stable order
+    pub fn sort(&mut self) {
+        self.components
+            .sort_by(|a, b| (&a.path, &a.name, &a.id).cmp(&(&b.path, &b.name, &b.id)));
+        self.edges
+            .sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+        for edge in &mut self.edges {
+            edge.usages
+                .sort_by_key(|usage| (usage.location.line, usage.location.column));
+        }
+        self.dead_code_candidates.sort();
+        self.adoption
+            .directories
+            .sort_by(|a, b| a.path.cmp(&b.path));
+    }
+}
+
+/// Serializes the projects of a report sorted by name, each with a sorted graph
+fn serialize_sorted<S: Serializer>(
+    projects: &[ProjectReport],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut projects = projects.to_vec();
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+    for project in &mut projects {
+        project.graph.sort();
+    }
+
+    projects.serialize(serializer)
+}
+
+impl Report {
+    /// Creates a report of the current version
+    pub fn new(projects: Vec<ProjectReport>) -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            projects,
+        }
+    }
+
+    /// Reads a report from JSON. Reports written before the format was versioned are a plain
+    /// array of projects and are read as well.
+    pub fn from_value(value: Value) -> Result<Self, String> {
+        if value.is_array() {
+            let projects = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            return Ok(Self::new(projects));
+        }
+
+        let report: Report = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        if report.schema_version > REPORT_SCHEMA_VERSION {
+            return Err(format!(
+                "The report has schema version {}, but this version of spinne only reads up to version {}",
+                report.schema_version, REPORT_SCHEMA_VERSION
+            ));
+        }
+
+        Ok(report)
+    }
+
+    /// Converts the report into JSON
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// The JSON Schema of the report format
+    pub fn json_schema() -> Value {
+        serde_json::to_value(schemars::schema_for!(Report)).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_schema_is_up_to_date() {
+        let published: Value = serde_json::from_str(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/schema/report.schema.json"
+        )))
+        .unwrap();
+
+        assert_eq!(
+            published,
+            Report::json_schema(),
+            "schema/report.schema.json is outdated, update it with `spinne schema`"
+        );
+    }
+
+    #[test]
+    fn test_from_value() {
+        let legacy = serde_json::json!([
+            {
+                "name": "app",
+                "graph": {
+                    "components": [{ "id": "1", "name": "App", "path": "app/src/App.tsx" }],
+                    "edges": []
+                }
+            }
+        ]);
+        let report = Report::from_value(legacy).unwrap();
+        assert_eq!(report.schema_version, REPORT_SCHEMA_VERSION);
+        assert_eq!(report.projects[0].graph.components[0].name, "App");
+        assert_eq!(report.projects[0].graph.components[0].reachable, None);
+
+        let roundtrip = Report::from_value(report.to_value()).unwrap();
+        assert_eq!(roundtrip, report);
+
+        let mut newer = report.to_value();
+        newer["schema_version"] = serde_json::json!(REPORT_SCHEMA_VERSION + 1);
+        assert!(Report::from_value(newer).is_err());
+    }
+
+    #[test]
+    fn test_stable_order() {
+        let component = |id: &str, name: &str, path: &str| ComponentReport {
+            id: id.to_string(),
+            name: name.to_string(),
+            path: PathBuf::from(path),
+            ..ComponentReport::default()
+        };
+        let edge = |from: &str, to: &str| EdgeReport {
+            from: from.to_string(),
+            to: to.to_string(),
+            count: 1,
+            ..EdgeReport::default()
+        };
+        let project = |name: &str, graph: GraphReport| ProjectReport {
+            name: name.to_string(),
+            graph,
+        };
+
+        let app = GraphReport {
+            components: vec![
+                component("c", "Page", "app/src/pages/Home.tsx"),
+                component("b", "Card", "app/src/components/Card.tsx"),
+                component("a", "Button", "app/src/components/Card.tsx"),
+            ],
+            edges: vec![edge("c", "b"), edge("b", "a"), edge("c", "a")],
+            dead_code_candidates: vec!["c".to_string(), "a".to_string()],
+            ..GraphReport::default()
+        };
+        let lib = GraphReport {
+            components: vec![component("d", "Icon", "lib/src/Icon.tsx")],
+            ..GraphReport::default()
+        };
+
+        let report = Report::new(vec![
+            project("lib", lib.clone()),
+            project("app", app.clone()),
+        ]);
+        let value = report.to_value();
+
+        assert_eq!(value["projects"][0]["name"], "app");
+        assert_eq!(value["projects"][1]["name"], "lib");
+        let graph = &value["projects"][0]["graph"];
+        let names: Vec<&str> = graph["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|component| component["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Button", "Card", "Page"]);
+        let edges: Vec<(&str, &str)> = graph["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| (edge["from"].as_str().unwrap(), edge["to"].as_str().unwrap()))
+            .collect();
+        assert_eq!(edges, vec![("b", "a"), ("c", "a"), ("c", "b")]);
+        assert_eq!(graph["dead_code_candidates"], serde_json::json!(["a", "c"]));
+
+        let mut reversed = app;
+        reversed.components.reverse();
+        reversed.edges.reverse();
+        let other = Report::new(vec![project("app", reversed), project("lib", lib)]);
+        assert_eq!(
+            serde_json::to_string(&other).unwrap(),
+            serde_json::to_string(&report).unwrap()
+        );
+    }
+}
@@ -1,9 +1,20 @@
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::analyze::component::{
+    ComponentKind, ComponentUsage, ComponentWrapper, DeclaredProp, PropValueStats, SourceLocation,
+};
+
+use super::{
+    adoption::Adoption,
+    diff::RegistryDiff,
+    export::{ExportOptions, GraphExport},
+    report::{AdoptionReport, ComponentReport, EdgeReport, GraphReport, ProjectReport, Report},
+};
 use std::{
-    collections::{HashMap, HashSet},
-    path::PathBuf,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
 };
-use sha2::{Digest, Sha256};
 
 /// Represents a component with its project context
 #[derive(Debug, Clone)]
@@ -15,22 +26,53 @@ pub struct ComponentNode {
     /// Path to the component's file
     pub file_path: PathBuf,
     /// Properties of the component
-    pub props: HashMap<String, usize>,
+    pub props: BTreeMap<String, usize>,
+    /// The values passed to each prop
+    pub prop_values: BTreeMap<String, PropValueStats>,
+    /// Props declared in the component's signature
+    pub declared_props: Vec<DeclaredProp>,
+    /// Whether the component is a function or class component
+    pub kind: ComponentKind,
+    /// Wrappers like `memo` or `forwardRef` applied to the component
+    pub wrappers: Vec<ComponentWrapper>,
+    /// Where the component is defined, if its definition was analyzed
+    pub location: Option<SourceLocation>,
+    /// Whether the component can be reached from an entry point of its project.
+    /// `None` if the project has no entry points configured.
+    pub reachable: Option<bool>,
+    /// Whether the component is exported from an entry point of its project
+    pub public_api: bool,
+    /// The installed version of the package the component is imported from
+    pub package_version: Option<String>,
+    /// Intrinsic elements like `div` rendered by the component, with usage counts
+    pub intrinsic_elements: BTreeMap<String, usize>,
+    /// Where each intrinsic element is rendered in the component's file
+    pub intrinsic_element_locations: BTreeMap<String, Vec<SourceLocation>>,
 }
 
 impl ComponentNode {
-    pub fn new(name: String, file_path: PathBuf, props: HashMap<String, usize>) -> Self {
+    pub fn new(name: String, file_path: PathBuf, props: BTreeMap<String, usize>) -> Self {
         let id = Self::compute_hash(&name, &file_path);
         Self {
             id,
             name,
             file_path,
             props,
+            prop_values: BTreeMap::new(),
+            declared_props: Vec::new(),
+            kind: ComponentKind::default(),
+            wrappers: Vec::new(),
+            location: None,
+            reachable: None,
+            public_api: false,
+            package_version: None,
+            intrinsic_elements: BTreeMap::new(),
+            intrinsic_element_locations: BTreeMap::new(),
         }
     }
 
     /// Computes a hash from a component's name and path
-    fn compute_hash(name: &str, path: &PathBuf) -> String {
+    fn compute_hash(name: &str, path: &Path) -> String {
         let mut hasher = Sha256::new();
         hasher.update(name.as_bytes());
         hasher.update(path.to_string_lossy().as_bytes());
@@ -44,6 +86,12 @@ impl ComponentNode {
 pub struct ComponentEdge {
     /// If cross-project, which project is being referenced
     pub project_context: Option<String>,
+    /// How many times the dependency is rendered
+    pub count: usize,
+    /// Props passed at the usage sites of this edge, with usage counts
+    pub props: BTreeMap<String, usize>,
+    /// Where the dependency is rendered in the dependent component's file
+    pub usages: Vec<ComponentUsage>,
 }
 
 /// Information about a component including its project context
@@ -95,6 +143,14 @@ pub struct ComponentRegistry {
     dependencies: HashMap<String, DependencyInfo>,
     /// Indices for looking up components
     indices: ComponentIndices,
+    /// Where the elements rendered in each project come from
+    adoption: HashMap<String, Adoption>,
+}
+
+impl Default for ComponentRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ComponentRegistry {
@@ -104,6 +160,7 @@ impl ComponentRegistry {
             components: HashMap::new(),
             dependencies: HashMap::new(),
             indices: ComponentIndices::default(),
+            adoption: HashMap::new(),
         }
     }
 
@@ -122,13 +179,11 @@ impl ComponentRegistry {
         self.components.insert(id.clone(), info);
 
         // Add to indices
-        self.indices.by_name.insert((name, project.clone()), id.clone());
+        self.indices
+            .by_name
+            .insert((name, project.clone()), id.clone());
         self.indices.by_path.insert(file_path, id.clone());
-        let project_components = self
-            .indices
-            .by_project
-            .entry(project)
-            .or_insert_with(HashSet::new);
+        let project_components = self.indices.by_project.entry(project).or_default();
         project_components.insert(id.clone());
 
         // Initialize empty dependency info
@@ -150,27 +205,50 @@ impl ComponentRegistry {
             return Err(format!("Target component {} not found", to));
         }
 
-        let edge = ComponentEdge { project_context };
-
-        // Add forward dependency
-        let from_deps = self
+        // Add forward dependency, keeping the instances of an existing edge
+        let from_deps = self.dependencies.entry(from.to_string()).or_default();
+        from_deps
             .dependencies
-            .entry(from.to_string())
-            .or_insert_with(DependencyInfo::default);
-        from_deps.dependencies.insert(to.to_string(), edge);
+            .entry(to.to_string())
+            .and_modify(|edge| edge.project_context = project_context.clone())
+            .or_insert(ComponentEdge {
+                project_context,
+                count: 0,
+                props: BTreeMap::new(),
+                usages: Vec::new(),
+            });
 
         // Add reverse dependency
-        let to_deps = self
-            .dependencies
-            .entry(to.to_string())
-            .or_insert_with(DependencyInfo::default);
+        let to_deps = self.dependencies.entry(to.to_string()).or_default();
         to_deps.dependents.insert(from.to_string());
 
         Ok(())
     }
 
+    /// Records a single rendering of a dependency with the props passed to it.
+    /// The dependency has to be added first.
+    pub fn add_instance(
+        &mut self,
+        from: &str,
+        to: &str,
+        usage: ComponentUsage,
+        props: &BTreeMap<String, usize>,
+    ) {
+        if let Some(edge) = self
+            .dependencies
+            .get_mut(from)
+            .and_then(|info| info.dependencies.get_mut(to))
+        {
+            edge.count += 1;
+            edge.usages.push(usage);
+            for (prop, count) in props {
+                *edge.props.entry(prop.clone()).or_insert(0) += *count;
+            }
+        }
+    }
+
     /// Adds a set of props to a component, incrementing existing counts
-    pub fn add_props(&mut self, component_id: &str, props: &HashMap<String, usize>) {
+    pub fn add_props(&mut self, component_id: &str, props: &BTreeMap<String, usize>) {
         if let Some(info) = self.components.get_mut(component_id) {
             for (prop, count) in props {
                 *info.node.props.entry(prop.clone()).or_insert(0) += *count;
@@ -178,6 +256,146 @@ impl ComponentRegistry {
         }
     }
 
+    /// Adds the values passed to the props of a component, incrementing existing counts
+    pub fn add_prop_values(
+        &mut self,
+        component_id: &str,
+        prop_values: &BTreeMap<String, PropValueStats>,
+    ) {
+        if let Some(info) = self.components.get_mut(component_id) {
+            for (prop, stats) in prop_values {
+                info.node
+                    .prop_values
+                    .entry(prop.clone())
+                    .or_default()
+                    .merge(stats);
+            }
+        }
+    }
+
+    /// Updates a component with the information only known from its definition.
+    /// Components can be registered through a usage before their own file is analyzed.
+    pub fn set_definition(&mut self, component_id: &str, definition: &ComponentNode) {
+        if let Some(info) = self.components.get_mut(component_id) {
+            info.node.declared_props = definition.declared_props.clone();
+            info.node.kind = definition.kind;
+            info.node.wrappers = definition.wrappers.clone();
+            info.node.location = definition.location;
+            info.node.intrinsic_elements = definition.intrinsic_elements.clone();
+            info.node.intrinsic_element_locations = definition.intrinsic_element_locations.clone();
+        }
+    }
+
+    /// Gets where the elements rendered in a project come from
+    pub fn get_adoption(&self, project: &str) -> Option<&Adoption> {
+        self.adoption.get(project)
+    }
+
+    /// Gets mutable access to the adoption of a project, creating it if it doesn't exist
+    pub fn get_adoption_mut(&mut self, project: &str) -> &mut Adoption {
+        self.adoption.entry(project.to_string()).or_default()
+    }
+
+    /// Merges the components and dependencies of another registry into this one.
+    /// Props, prop values, instances and adoption are added to the existing counts, definitions
+    /// and entry point information replace the existing ones.
+    pub fn merge(&mut self, other: &ComponentRegistry) {
+        for (project, adoption) in &other.adoption {
+            self.get_adoption_mut(project).merge(adoption);
+        }
+
+        for (id, info) in &other.components {
+            if !self.components.contains_key(id) {
+                self.add_component(info.node.clone(), info.project.clone());
+                continue;
+            }
+
+            self.add_props(id, &info.node.props);
+            self.add_prop_values(id, &info.node.prop_values);
+            if info.node.location.is_some() {
+                self.set_definition(id, &info.node);
+            }
+            if let Some(existing) = self.components.get_mut(id) {
+                if info.node.reachable.is_some() {
+                    existing.node.reachable = info.node.reachable;
+                }
+                existing.node.public_api |= info.node.public_api;
+                if existing.node.package_version.is_none() {
+                    existing.node.package_version = info.node.package_version.clone();
+                }
+            }
+        }
+
+        for (from, deps) in &other.dependencies {
+            for (to, edge) in &deps.dependencies {
+                if self
+                    .add_dependency(from, to, edge.project_context.clone())
+                    .is_err()
+                {
+                    continue;
+                }
+
+                if let Some(existing) = self
+                    .dependencies
+                    .get_mut(from)
+                    .and_then(|info| info.dependencies.get_mut(to))
+                {
+                    existing.count += edge.count;
+                    existing.usages.extend(edge.usages.iter().cloned());
+                    for (prop, count) in &edge.props {
+                        *existing.props.entry(prop.clone()).or_insert(0) += *count;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Marks the components of a project that are reachable from its entry points.
+    /// `root_ids` are the components defined or rendered in the entry points, `public_ids` the
+    /// components exported from them. Every other component of the project becomes unreachable.
+    pub fn set_entry_points(&mut self, project: &str, root_ids: &[String], public_ids: &[String]) {
+        let project_ids = self
+            .indices
+            .by_project
+            .get(project)
+            .cloned()
+            .unwrap_or_default();
+
+        for id in &project_ids {
+            if let Some(info) = self.components.get_mut(id) {
+                info.node.reachable = Some(false);
+            }
+        }
+
+        for id in public_ids {
+            if let Some(info) = self.components.get_mut(id) {
+                info.node.public_api = true;
+            }
+        }
+
+        let reachable_ids: HashSet<String> = root_ids
+            .iter()
+            .chain(public_ids)
+            .flat_map(|id| self.traverse_from(id))
+            .map(|node| node.component_id)
+            .filter(|id| project_ids.contains(id))
+            .collect();
+
+        for id in reachable_ids {
+            if let Some(info) = self.components.get_mut(&id) {
+                info.node.reachable = Some(true);
+            }
+        }
+    }
+
+    /// Gets the components of a project that are not reachable from any of its entry points
+    pub fn get_unreachable_components(&self, project: &str) -> Vec<&ComponentInfo> {
+        self.get_project_components(project)
+            .into_iter()
+            .filter(|info| info.node.reachable == Some(false))
+            .collect()
+    }
+
     /// Gets a component by its ID
     pub fn get_component(&self, id: &str) -> Option<&ComponentInfo> {
         self.components.get(id)
@@ -199,6 +417,11 @@ impl ComponentRegistry {
             .and_then(|id| self.components.get(id))
     }
 
+    /// Gets all components of all projects
+    pub fn get_components(&self) -> Vec<&ComponentInfo> {
+        self.components.values().collect()
+    }
+
     /// Gets all components in a project
     pub fn get_project_components(&self, project: &str) -> Vec<&ComponentInfo> {
         self.indices
@@ -233,7 +456,8 @@ impl ComponentRegistry {
             .unwrap_or_default()
     }
 
-    /// Traverses the graph starting from a component
+    /// Traverses the graph depth-first starting from a component. Dependencies are visited sorted
+    /// by name and every component is visited once.
     pub fn traverse_from(&self, start_id: &str) -> Vec<TraversalNode> {
         let mut visited = HashSet::new();
         let mut result = Vec::new();
@@ -255,9 +479,16 @@ impl ComponentRegistry {
         visited.insert(component_id.clone());
 
         if let Some(component) = self.components.get(&component_id) {
-            let deps = self.get_dependencies(&component_id);
-            let dep_ids: Vec<String> = deps.iter().map(|(id, _)| id.clone()).collect();
-            
+            let mut dep_ids: Vec<String> = self
+                .get_dependencies(&component_id)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            dep_ids.sort_by_cached_key(|id| {
+                let name = self.components.get(id).map(|info| info.node.name.clone());
+                (name, id.clone())
+            });
+
             result.push(TraversalNode {
                 component_id: component_id.clone(),
                 component_name: component.node.name.clone(),
@@ -304,61 +535,149 @@ impl ComponentRegistry {
         }
     }
 
-    /// Converts the registry into a serializable format for JSON output
-    pub fn to_serializable(&self) -> Value {
+    /// Converts the registry into the report format
+    pub fn to_report(&self) -> Report {
         // Group components by project
-        let mut projects = HashMap::new();
+        let mut projects: HashMap<String, GraphReport> = HashMap::new();
 
         for (id, info) in &self.components {
-            let project = info.project.clone();
-            let entry = projects.entry(project).or_insert_with(|| {
-                serde_json::json!({
-                    "components": Vec::<Value>::new(),
-                    "edges": Vec::<Value>::new()
-                })
-            });
+            let graph = projects
+                .entry(info.project.clone())
+                .or_insert_with(|| GraphReport {
+                    adoption: self
+                        .adoption
+                        .get(&info.project)
+                        .map(Adoption::to_report)
+                        .unwrap_or_default(),
+                    ..GraphReport::default()
+                });
 
-            // Add component
-            let component_json = serde_json::json!({
-                "id": id,
-                "name": info.node.name,
-                "path": info.node.file_path,
-                "props": info.node.props
+            graph.components.push(ComponentReport {
+                id: id.clone(),
+                name: info.node.name.clone(),
+                path: info.node.file_path.clone(),
+                props: info.node.props.clone(),
+                prop_values: info.node.prop_values.clone(),
+                declared_props: info.node.declared_props.clone(),
+                kind: info.node.kind,
+                wrappers: info.node.wrappers.clone(),
+                location: info.node.location,
+                reachable: info.node.reachable,
+                public_api: info.node.public_api,
+                package_version: info.node.package_version.clone(),
+                intrinsic_elements: info.node.intrinsic_elements.clone(),
             });
-            entry["components"]
-                .as_array_mut()
-                .unwrap()
-                .push(component_json);
 
-            // Add edges
+            if info.node.reachable == Some(false) {
+                graph.dead_code_candidates.push(id.clone());
+            }
+
             if let Some(deps) = self.dependencies.get(id) {
                 for (target_id, edge) in &deps.dependencies {
-                    let edge_json = serde_json::json!({
-                        "from": id,
-                        "to": target_id,
-                        "project_context": edge.project_context
+                    graph.edges.push(EdgeReport {
+                        from: id.clone(),
+                        to: target_id.clone(),
+                        project_context: edge.project_context.clone(),
+                        count: edge.count,
+                        props: edge.props.clone(),
+                        usages: edge.usages.clone(),
                     });
-                    entry["edges"].as_array_mut().unwrap().push(edge_json);
                 }
             }
         }
 
-        // Convert to final format
-        let mut result = Vec::new();
-        for (project_name, data) in projects {
-            result.push(serde_json::json!({
-                "name": project_name,
-                "graph": data
-            }));
+        Report::new(
+            projects
+                .into_iter()
+                .map(|(name, graph)| ProjectReport { name, graph })
+                .collect(),
+        )
+    }
+
+    /// Converts the registry into a serializable format for JSON output
+    pub fn to_serializable(&self) -> Value {
+        self.to_report().to_value()
+    }
+
+    /// Reads a registry back from a report, e.g. the report of an earlier run
+    pub fn from_report(report: &Report) -> Result<Self, String> {
+        let mut registry = Self::new();
+
+        for project in &report.projects {
+            if project.graph.adoption != AdoptionReport::default() {
+                registry.adoption.insert(
+                    project.name.clone(),
+                    Adoption::from_report(&project.graph.adoption),
+                );
+            }
+
+            for component in &project.graph.components {
+                let mut node = ComponentNode::new(
+                    component.name.clone(),
+                    component.path.clone(),
+                    component.props.clone(),
+                );
+                if !component.id.is_empty() {
+                    node.id = component.id.clone();
+                }
+                node.prop_values = component.prop_values.clone();
+                node.declared_props = component.declared_props.clone();
+                node.kind = component.kind;
+                node.wrappers = component.wrappers.clone();
+                node.location = component.location;
+                node.reachable = component.reachable;
+                node.public_api = component.public_api;
+                node.package_version = component.package_version.clone();
+                node.intrinsic_elements = component.intrinsic_elements.clone();
+
+                registry.add_component(node, project.name.clone());
+            }
         }
 
-        serde_json::Value::Array(result)
+        // edges can point to components of projects that come later in the report
+        for edge in report
+            .projects
+            .iter()
+            .flat_map(|project| &project.graph.edges)
+        {
+            registry.add_dependency(&edge.from, &edge.to, edge.project_context.clone())?;
+
+            if let Some(component_edge) = registry
+                .dependencies
+                .get_mut(&edge.from)
+                .and_then(|info| info.dependencies.get_mut(&edge.to))
+            {
+                component_edge.count = edge.count;
+                component_edge.props = edge.props.clone();
+                component_edge.usages = edge.usages.clone();
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Reads a registry back from the output of `to_serializable`, including reports written
+    /// before the format was versioned
+    pub fn from_serializable(value: &Value) -> Result<Self, String> {
+        Self::from_report(&Report::from_value(value.clone())?)
+    }
+
+    /// Compares the registry with a newer one, e.g. the report of the main branch with the one of
+    /// a pull request
+    pub fn diff(&self, newer: &ComponentRegistry) -> RegistryDiff {
+        RegistryDiff::new(self, newer)
+    }
+
+    /// Selects the part of the registry to render in graph formats like Graphviz DOT or Mermaid
+    pub fn export(&self, options: &ExportOptions) -> Result<GraphExport<'_>, String> {
+        GraphExport::new(self, options)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyze::component::PropSpread;
 
     #[test]
     fn test_add_component() {
@@ -366,7 +685,7 @@ mod tests {
         let component = ComponentNode::new(
             "TestComponent".to_string(),
             PathBuf::from("src/TestComponent.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
         registry.add_component(component.clone(), "test-project".to_string());
 
@@ -382,12 +701,12 @@ mod tests {
         let component1 = ComponentNode::new(
             "Component1".to_string(),
             PathBuf::from("src/Component1.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
         let component2 = ComponentNode::new(
             "Component2".to_string(),
             PathBuf::from("src/Component2.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
 
         registry.add_component(component1.clone(), "test-project".to_string());
@@ -407,12 +726,12 @@ mod tests {
         let component1 = ComponentNode::new(
             "Component1".to_string(),
             PathBuf::from("src/Component1.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
         let component2 = ComponentNode::new(
             "Component2".to_string(),
             PathBuf::from("src/Component2.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
 
         registry.add_component(component1.clone(), "project1".to_string());
@@ -433,17 +752,17 @@ mod tests {
         let component1 = ComponentNode::new(
             "Component1".to_string(),
             PathBuf::from("src/Component1.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
         let component2 = ComponentNode::new(
             "Component2".to_string(),
             PathBuf::from("src/Component2.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
         let component3 = ComponentNode::new(
             "Component3".to_string(),
             PathBuf::from("src/Component3.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
 
         registry.add_component(component1.clone(), "test-project".to_string());
@@ -470,12 +789,12 @@ mod tests {
         let component1 = ComponentNode::new(
             "Component1".to_string(),
             PathBuf::from("src/Component1.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
         let component2 = ComponentNode::new(
             "Component2".to_string(),
             PathBuf::from("src/Component2.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
 
         registry.add_component(component1.clone(), "test-project".to_string());
@@ -496,16 +815,16 @@ mod tests {
         let component = ComponentNode::new(
             "Button".to_string(),
             PathBuf::from("src/Button.tsx"),
-            HashMap::new(),
+            BTreeMap::new(),
         );
 
         registry.add_component(component.clone(), "test-project".to_string());
 
-        let mut props1 = HashMap::new();
+        let mut props1 = BTreeMap::new();
         props1.insert("label".to_string(), 1);
         registry.add_props(&component.id, &props1);
 
-        let mut props2 = HashMap::new();
+        let mut props2 = BTreeMap::new();
         props2.insert("label".to_string(), 2);
         props2.insert("onClick".to_string(), 1);
         registry.add_props(&component.id, &props2);
@@ -514,4 +833,276 @@ mod tests {
         assert_eq!(stored.node.props.get("label"), Some(&3));
         assert_eq!(stored.node.props.get("onClick"), Some(&1));
     }
+
+    #[test]
+    fn test_add_instance() {
+        let mut registry = ComponentRegistry::new();
+        let app = ComponentNode::new(
+            "App".to_string(),
+            PathBuf::from("src/App.tsx"),
+            BTreeMap::new(),
+        );
+        let button = ComponentNode::new(
+            "Button".to_string(),
+            PathBuf::from("src/Button.tsx"),
+            BTreeMap::new(),
+        );
+
+        registry.add_component(app.clone(), "test-project".to_string());
+        registry.add_component(button.clone(), "test-project".to_string());
+
+        let first = ComponentUsage {
+            location: SourceLocation {
+                line: 3,
+                column: 12,
+            },
+            props: vec!["variant".to_string()],
+            spreads: Vec::new(),
+            has_unknown_spread: false,
+        };
+        let second = ComponentUsage {
+            location: SourceLocation {
+                line: 8,
+                column: 16,
+            },
+            props: vec!["label".to_string(), "variant".to_string()],
+            spreads: vec![PropSpread {
+                source: "rest".to_string(),
+                resolved: false,
+            }],
+            has_unknown_spread: true,
+        };
+
+        registry.add_dependency(&app.id, &button.id, None).unwrap();
+        registry.add_instance(
+            &app.id,
+            &button.id,
+            first.clone(),
+            &BTreeMap::from([("variant".to_string(), 1)]),
+        );
+        // adding the same dependency again keeps the recorded instances
+        registry.add_dependency(&app.id, &button.id, None).unwrap();
+        registry.add_instance(
+            &app.id,
+            &button.id,
+            second.clone(),
+            &BTreeMap::from([("variant".to_string(), 1), ("label".to_string(), 1)]),
+        );
+
+        let dependencies = registry.get_dependencies(&app.id);
+        assert_eq!(dependencies.len(), 1);
+
+        let edge = dependencies[0].1;
+        assert_eq!(edge.count, 2);
+        assert_eq!(edge.props.get("variant"), Some(&2));
+        assert_eq!(edge.props.get("label"), Some(&1));
+        assert_eq!(edge.usages, vec![first, second]);
+
+        let serialized = registry.to_serializable();
+        let edges = serialized["projects"][0]["graph"]["edges"]
+            .as_array()
+            .unwrap();
+        assert_eq!(edges[0]["count"], 2);
+        assert_eq!(edges[0]["props"]["variant"], 2);
+        assert_eq!(edges[0]["usages"][1]["line"], 8);
+        assert_eq!(edges[0]["usages"][1]["has_unknown_spread"], true);
+    }
+
+    #[test]
+    fn test_from_serializable() {
+        let mut registry = ComponentRegistry::new();
+        let mut app = ComponentNode::new(
+            "App".to_string(),
+            PathBuf::from("app/src/App.tsx"),
+            BTreeMap::new(),
+        );
+        app.location = Some(SourceLocation { line: 3, column: 8 });
+        app.intrinsic_elements = BTreeMap::from([("div".to_string(), 1)]);
+        let button = ComponentNode::new(
+            "Button".to_string(),
+            PathBuf::from("ui/src/Button.tsx"),
+            BTreeMap::from([("variant".to_string(), 1)]),
+        );
+        registry.add_component(app.clone(), "app".to_string());
+        registry.add_component(button.clone(), "ui".to_string());
+        registry
+            .add_dependency(&app.id, &button.id, Some("ui".to_string()))
+            .unwrap();
+        registry.add_instance(
+            &app.id,
+            &button.id,
+            ComponentUsage {
+                location: SourceLocation {
+                    line: 5,
+                    column: 12,
+                },
+                props: vec!["variant".to_string()],
+                spreads: Vec::new(),
+                has_unknown_spread: false,
+            },
+            &BTreeMap::from([("variant".to_string(), 1)]),
+        );
+
+        registry.get_adoption_mut("app").add_intrinsic_elements(
+            Path::new("app/src"),
+            &BTreeMap::from([("div".to_string(), 1)]),
+        );
+
+        let loaded = ComponentRegistry::from_serializable(&registry.to_serializable()).unwrap();
+
+        assert_eq!(loaded.get_adoption("app"), registry.get_adoption("app"));
+        assert_eq!(loaded.get_adoption("ui"), None);
+
+        assert_eq!(loaded.to_report().projects.len(), 2);
+        let loaded_app = loaded.find_component("App", "app").unwrap();
+        assert_eq!(loaded_app.node.id, app.id);
+        assert_eq!(loaded_app.node.location, app.location);
+        assert_eq!(loaded_app.node.intrinsic_elements, app.intrinsic_elements);
+        assert_eq!(
+            loaded.find_component("Button", "ui").unwrap().node.props,
+            button.props
+        );
+
+        let dependencies = loaded.get_dependencies(&app.id);
+        assert_eq!(dependencies[0].0, button.id);
+        assert_eq!(dependencies[0].1.project_context, Some("ui".to_string()));
+        assert_eq!(dependencies[0].1.count, 1);
+        assert_eq!(dependencies[0].1.usages[0].props, vec!["variant"]);
+        assert_eq!(loaded.get_dependents(&button.id), vec![app.id.clone()]);
+
+        assert!(ComponentRegistry::from_serializable(&serde_json::json!({})).is_err());
+        // reports written before the format was versioned are a plain array of projects
+        let legacy = registry.to_serializable()["projects"].clone();
+        assert_eq!(
+            ComponentRegistry::from_serializable(&legacy)
+                .unwrap()
+                .get_dependencies(&app.id)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let app = ComponentNode::new(
+            "App".to_string(),
+            PathBuf::from("app/src/App.tsx"),
+            BTreeMap::new(),
+        );
+        let mut button = ComponentNode::new(
+            "Button".to_string(),
+            PathBuf::from("ui/src/Button.tsx"),
+            BTreeMap::from([("variant".to_string(), 1)]),
+        );
+        button.location = Some(SourceLocation { line: 2, column: 8 });
+        let usage = ComponentUsage {
+            location: SourceLocation { line: 4, column: 4 },
+            props: vec!["variant".to_string()],
+            spreads: Vec::new(),
+            has_unknown_spread: false,
+        };
+
+        let mut workspace = ComponentRegistry::new();
+        workspace.add_component(button.clone(), "ui".to_string());
+
+        // the consumer only knows the identity of the button and adds its own usage on top
+        let mut consumer = ComponentRegistry::new();
+        consumer.add_component(app.clone(), "app".to_string());
+        consumer.add_component(
+            ComponentNode::new(
+                button.name.clone(),
+                button.file_path.clone(),
+                BTreeMap::from([("variant".to_string(), 2)]),
+            ),
+            "ui".to_string(),
+        );
+        consumer
+            .add_dependency(&app.id, &button.id, Some("ui".to_string()))
+            .unwrap();
+        for _ in 0..2 {
+            consumer.add_instance(
+                &app.id,
+                &button.id,
+                usage.clone(),
+                &BTreeMap::from([("variant".to_string(), 1)]),
+            );
+        }
+
+        workspace.merge(&consumer);
+        workspace.merge(&consumer);
+
+        let merged_button = &workspace.get_component(&button.id).unwrap().node;
+        assert_eq!(merged_button.props.get("variant"), Some(&5));
+        assert_eq!(merged_button.location, button.location);
+        assert!(workspace.find_component("App", "app").is_some());
+        assert_eq!(workspace.get_dependents(&button.id), vec![app.id.clone()]);
+
+        let dependencies = workspace.get_dependencies(&app.id);
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].1.count, 4);
+        assert_eq!(dependencies[0].1.props.get("variant"), Some(&4));
+        assert_eq!(dependencies[0].1.usages.len(), 4);
+    }
+
+    #[test]
+    fn test_set_entry_points() {
+        let mut registry = ComponentRegistry::new();
+        let component = |name: &str| {
+            ComponentNode::new(
+                name.to_string(),
+                PathBuf::from(format!("src/{}.tsx", name)),
+                BTreeMap::new(),
+            )
+        };
+        let app = component("App");
+        let header = component("Header");
+        let button = component("Button");
+        let legacy = component("Legacy");
+        let shared = component("Shared");
+
+        for node in [&app, &header, &button, &legacy] {
+            registry.add_component(node.clone(), "test-project".to_string());
+        }
+        registry.add_component(shared.clone(), "other-project".to_string());
+
+        registry.add_dependency(&app.id, &header.id, None).unwrap();
+        registry
+            .add_dependency(&header.id, &shared.id, None)
+            .unwrap();
+        registry
+            .add_dependency(&legacy.id, &header.id, None)
+            .unwrap();
+
+        registry.set_entry_points(
+            "test-project",
+            std::slice::from_ref(&app.id),
+            std::slice::from_ref(&button.id),
+        );
+
+        let node = |id: &str| &registry.get_component(id).unwrap().node;
+        assert_eq!(node(&app.id).reachable, Some(true));
+        assert_eq!(node(&header.id).reachable, Some(true));
+        assert_eq!(node(&button.id).reachable, Some(true));
+        assert!(node(&button.id).public_api);
+        assert!(!node(&app.id).public_api);
+        assert_eq!(node(&legacy.id).reachable, Some(false));
+        // components of other projects are not affected
+        assert_eq!(node(&shared.id).reachable, None);
+
+        let unreachable = registry.get_unreachable_components("test-project");
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(unreachable[0].node.id, legacy.id);
+
+        let serialized = registry.to_serializable();
+        let project = serialized["projects"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|project| project["name"] == "test-project")
+            .unwrap();
+        assert_eq!(
+            project["graph"]["dead_code_candidates"],
+            serde_json::json!([legacy.id])
+        );
+    }
 }
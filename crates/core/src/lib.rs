@@ -1,4 +1,18 @@
+// lints the existing tests were written without
+#![cfg_attr(
+    test,
+    allow(
+        clippy::bool_assert_comparison,
+        clippy::needless_borrow,
+        clippy::needless_borrows_for_generic_args,
+        clippy::useless_conversion,
+        clippy::useless_vec
+    )
+)]
+
 mod analyze;
+mod cache;
+mod check;
 mod config;
 mod graph;
 mod package_json;
@@ -6,9 +20,18 @@ mod parse;
 mod traverse;
 mod util;
 
+pub use check::{
+    Baseline, ComponentRule, DependencyRule, IntrinsicElementRule, LimitRule, PropRule, RuleKind,
+    Rules, Severity, Violation,
+};
 pub use config::Config;
-pub use graph::ComponentGraph;
+pub use graph::{
+    AdoptionReport, Component, ComponentGraph, ComponentRef, ComponentRegistry, ComponentReport,
+    DiffReport, DiffSummary, DirectoryAdoptionReport, EdgeChange, EdgeReport, ElementCountsReport,
+    ElementPercentagesReport, ExportEdge, ExportOptions, GraphExport, GraphReport,
+    ProjectDependency, ProjectReport, PropChange, RegistryDiff, Report, REPORT_SCHEMA_VERSION,
+};
 pub use package_json::PackageJson;
 pub use traverse::project_types::{ConsumerProject, Project, SourceProject};
-pub use traverse::ProjectResolver;
 pub use traverse::Workspace;
+pub use traverse::{PackageResolver, ProjectResolver};
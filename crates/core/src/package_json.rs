@@ -11,6 +11,8 @@ pub struct PackageJson {
     pub path: PathBuf,
     /// The name of the project.
     pub name: Option<String>,
+    /// The version of the project.
+    pub version: Option<String>,
     /// The workspaces of the project.
     pub workspaces: Option<Vec<String>>,
     /// The dependencies of the project.
@@ -34,10 +36,12 @@ impl PackageJson {
             return None;
         }
 
-        let mut package_json = Self::default();
-        package_json.path = path.clone();
+        let mut package_json = Self {
+            path: path.clone(),
+            ..Self::default()
+        };
 
-        match fs::read_to_string(&path) {
+        match fs::read_to_string(path) {
             Ok(content) => match serde_json::from_str::<Value>(&content) {
                 Ok(mut parsed) => {
                     if let Some(json_object) = parsed.as_object_mut() {
@@ -55,6 +59,12 @@ impl PackageJson {
                             .and_then(|field| field.as_str())
                             .map(ToString::to_string);
 
+                        // Add version
+                        package_json.version = json_object
+                            .get("version")
+                            .and_then(|field| field.as_str())
+                            .map(ToString::to_string);
+
                         // Add workspaces
                         package_json.workspaces =
                             Self::get_workspaces(json_object.get("workspaces"));
@@ -149,19 +159,21 @@ impl PackageJson {
         })
     }
 
-    // TODO: resolve workspaces with blob support
+    /// Reads the workspace patterns, either from an array (`"workspaces": ["packages/*"]`)
+    /// or from the `packages` field of an object (`"workspaces": { "packages": ["packages/*"] }`)
     fn get_workspaces(json: Option<&Value>) -> Option<Vec<String>> {
-        let workspaces = json.and_then(|field| field.as_array());
-
-        match workspaces {
-            Some(workspaces) => Some(
-                workspaces
-                    .iter()
-                    .map(|item| item.as_str().unwrap().to_string())
-                    .collect(),
-            ),
-            None => None,
-        }
+        let workspaces = json.and_then(|field| match field {
+            Value::Object(object) => object.get("packages").and_then(|field| field.as_array()),
+            _ => field.as_array(),
+        });
+
+        workspaces.map(|workspaces| {
+            workspaces
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(ToString::to_string)
+                .collect()
+        })
     }
 }
 
@@ -188,6 +200,7 @@ mod tests {
             PackageJson::read(&PathBuf::from(temp_dir.path().join("package.json")), true)
                 .expect("Failed to read package.json");
         assert_eq!(package_json.name, Some("test-project".to_string()));
+        assert_eq!(package_json.version, Some("1.0.0".to_string()));
     }
 
     #[test]
@@ -235,6 +248,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolves_workspaces_object() {
+        let temp_dir = create_mock_project(&vec![(
+            "package.json",
+            r#"
+        {
+            "name": "test-project",
+            "workspaces": {
+                "packages": ["packages/*", "apps/*"],
+                "nohoist": ["**/react-native"]
+            }
+        }"#,
+        )]);
+
+        let package_json = PackageJson::read(&temp_dir.path().join("package.json"), true).unwrap();
+
+        assert_eq!(
+            package_json.workspaces,
+            Some(vec!["packages/*".to_string(), "apps/*".to_string()])
+        );
+    }
+
     #[test]
     fn test_get_all_dependencies() {
         let temp_dir = create_mock_project(&vec![(
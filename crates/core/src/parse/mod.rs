@@ -1,3 +1,3 @@
-mod tsx;
+mod source;
 
-pub use tsx::parse_tsx;
+pub use source::{is_supported_file, parse_source};
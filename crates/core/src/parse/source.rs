@@ -0,0 +1,126 @@
+use std::path::Path;
+
+use spinne_logger::Logger;
+
+use itertools::Itertools;
+use miette::NamedSource;
+use oxc_allocator::Allocator;
+use oxc_parser::{Parser, ParserReturn};
+use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn};
+use oxc_span::SourceType;
+
+/// File extensions that can be analyzed
+const SUPPORTED_EXTENSIONS: [&str; 8] = ["tsx", "ts", "jsx", "js", "mjs", "cjs", "mts", "cts"];
+
+/// Check if the file is a JavaScript or TypeScript file that can be analyzed
+pub fn is_supported_file(file_path: &Path) -> bool {
+    file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SUPPORTED_EXTENSIONS.contains(&ext))
+}
+
+/// Get the source type from the file extension.
+/// Files without a known extension are parsed as TypeScript with JSX.
+fn get_source_type(file_path: &Path) -> SourceType {
+    SourceType::from_path(file_path)
+        .unwrap_or_else(|_| SourceType::default().with_typescript(true).with_jsx(true))
+}
+
+/// parse a javascript or typescript file with oxc
+pub fn parse_source<'a>(
+    allocator: &'a Allocator,
+    file_path: &Path,
+    file_content: &'a str,
+) -> Result<(ParserReturn<'a>, SemanticBuilderReturn<'a>), String> {
+    let source_type = get_source_type(file_path);
+
+    let parser = Parser::new(allocator, file_content, source_type);
+    let parser_ret = parser.parse();
+    let named_source = NamedSource::new(file_path.to_string_lossy(), file_content.to_string());
+
+    if !parser_ret.errors.is_empty() {
+        let error_message: String = parser_ret
+            .errors
+            .into_iter()
+            .map(|error| format!("{:?}", error.with_source_code(named_source.clone())))
+            .join("\n");
+        Logger::error(&format!("Parsing failed:\n\n{error_message}"));
+        return Err(error_message);
+    }
+
+    let semantic_ret = SemanticBuilder::new().build(&parser_ret.program);
+
+    if !semantic_ret.errors.is_empty() {
+        let error_message: String = semantic_ret
+            .errors
+            .into_iter()
+            .map(|error| format!("{:?}", error.with_source_code(named_source.clone())))
+            .join("\n");
+        Logger::error(&format!("Parsing failed:\n\n{error_message}"));
+        return Err(error_message);
+    }
+
+    Ok((parser_ret, semantic_ret))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_tsx() {
+        let allocator = Allocator::default();
+        let file_path = PathBuf::from("test.tsx");
+        let file_content = "const App = () => <div>Hello, world!</div>;";
+        let result = parse_source(&allocator, &file_path, &file_content);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_tsx_with_error() {
+        let allocator = Allocator::default();
+        let file_path = PathBuf::from("test.tsx");
+        let file_content = "const App = im code with errors => <div>Hello, world!</div>;";
+        let result = parse_source(&allocator, &file_path, &file_content);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_jsx() {
+        let allocator = Allocator::default();
+        let file_content = "export const App = ({ title }) => <div>{title}</div>;";
+
+        for file_name in ["test.jsx", "test.js", "test.mjs"] {
+            let result = parse_source(&allocator, &PathBuf::from(file_name), file_content);
+            assert!(result.is_ok(), "{} should parse", file_name);
+        }
+    }
+
+    #[test]
+    fn test_parse_uses_source_type_of_extension() {
+        let allocator = Allocator::default();
+        // type annotations are only valid in typescript files
+        let file_content = "const add = (a: number, b: number) => a + b;";
+
+        assert!(parse_source(&allocator, &PathBuf::from("add.mts"), file_content).is_ok());
+        assert!(parse_source(&allocator, &PathBuf::from("add.cts"), file_content).is_ok());
+        assert!(parse_source(&allocator, &PathBuf::from("add.js"), file_content).is_err());
+    }
+
+    #[test]
+    fn test_is_supported_file() {
+        for file_name in [
+            "a.tsx", "a.ts", "a.jsx", "a.js", "a.mjs", "a.cjs", "a.mts", "a.cts",
+        ] {
+            assert!(is_supported_file(&PathBuf::from(file_name)));
+        }
+
+        assert!(!is_supported_file(&PathBuf::from("a.css")));
+        assert!(!is_supported_file(&PathBuf::from("README")));
+    }
+}
@@ -2,6 +2,7 @@ mod package;
 pub mod project_types;
 mod resolver;
 mod workspace;
+mod workspace_members;
 
 pub use package::*;
 pub use resolver::*;
@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    sync::Mutex,
 };
 
 use crate::package_json::PackageJson;
@@ -12,6 +13,18 @@ pub enum PackageResolverError {
     PackageJsonNotFound(PathBuf),
 }
 
+impl std::fmt::Display for PackageResolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageResolverError::IoError(error) => write!(f, "IO error: {}", error),
+            PackageResolverError::JsonError(error) => write!(f, "JSON error: {}", error),
+            PackageResolverError::PackageJsonNotFound(path) => {
+                write!(f, "No package.json found for {}", path.display())
+            }
+        }
+    }
+}
+
 impl From<std::io::Error> for PackageResolverError {
     fn from(error: std::io::Error) -> Self {
         PackageResolverError::IoError(error)
@@ -29,23 +42,37 @@ impl From<serde_json::Error> for PackageResolverError {
 ///
 /// Mainly used for node_modules resolution and resolving imports from other packages
 /// The resulting package name is the name of the package that contains the file and can be used to create edges between packages
-#[derive(Clone)]
 pub struct PackageResolver {
-    /// Cache of path -> package name to avoid reading the same package.json files multiple times
-    cache: HashMap<PathBuf, String>,
+    /// Cache of path -> package name to avoid reading the same package.json files multiple times.
+    /// Shared by all files analyzed in parallel.
+    cache: Mutex<HashMap<PathBuf, String>>,
+}
+
+impl Clone for PackageResolver {
+    fn clone(&self) -> Self {
+        Self {
+            cache: Mutex::new(self.cache.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl Default for PackageResolver {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PackageResolver {
     pub fn new() -> Self {
         Self {
-            cache: HashMap::new(),
+            cache: Mutex::new(HashMap::new()),
         }
     }
 
     /// Get the package name for a file path by finding and parsing the nearest package.json
-    pub fn get_package_name(&mut self, path: &Path) -> Result<String, PackageResolverError> {
+    pub fn get_package_name(&self, path: &Path) -> Result<String, PackageResolverError> {
         // Check if we have this path cached
-        if let Some(package_name) = self.cache.get(path) {
+        if let Some(package_name) = self.cache.lock().unwrap().get(path) {
             return Ok(package_name.clone());
         }
 
@@ -61,7 +88,10 @@ impl PackageResolver {
 
         if let Some(package_name) = package_name {
             // Cache the result for all files under this package
-            self.cache.insert(path.to_path_buf(), package_name.clone());
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf(), package_name.clone());
 
             Ok(package_name)
         } else {
@@ -73,7 +103,7 @@ impl PackageResolver {
 
     /// Check if two paths belong to the same package by comparing their package names
     pub fn is_same_package(
-        &mut self,
+        &self,
         path1: &Path,
         path2: &Path,
     ) -> Result<bool, PackageResolverError> {
@@ -120,7 +150,7 @@ mod tests {
         ];
         let temp_dir = create_mock_project(&files);
 
-        let mut resolver = PackageResolver::new();
+        let resolver = PackageResolver::new();
         let package_name = resolver
             .get_package_name(&temp_dir.path().join("src/index.js"))
             .unwrap();
@@ -137,7 +167,7 @@ mod tests {
         ];
         let temp_dir = create_mock_project(&files);
 
-        let mut resolver = PackageResolver::new();
+        let resolver = PackageResolver::new();
 
         // This should be true because the two files are in the same package
         assert!(resolver
@@ -162,7 +192,7 @@ mod tests {
         ];
         let temp_dir = create_mock_project(&files);
 
-        let mut resolver = PackageResolver::new();
+        let resolver = PackageResolver::new();
 
         // This should be false because the two files are in different packages
         assert!(!resolver
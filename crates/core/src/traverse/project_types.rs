@@ -1,29 +1,35 @@
 use std::{
     any::Any,
-    collections::{HashMap, HashSet},
-    path::PathBuf,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
 };
 
 use crate::{
-    analyze::react::analyzer::ReactAnalyzer,
+    analyze::{
+        component::{Component, ComponentUsage, ElementSource},
+        react::{analyzer::ReactAnalyzer, find_entry_components},
+    },
+    cache::{hash_file, AnalysisCache},
     config::{Config, ConfigValues},
     graph::{ComponentNode, ComponentRegistry},
     package_json::PackageJson,
-    parse::parse_tsx,
-    traverse::{PackageResolver, ProjectResolver},
+    parse::{is_supported_file, parse_source},
+    traverse::{PackageResolver, ProjectResolver, SourceDependency},
     util::replace_absolute_path_with_project_name,
 };
+use rayon::prelude::*;
 use spinne_logger::Logger;
 
-/// Trait defining common functionality for all project types
-pub trait Project: Any {
+/// Trait defining common functionality for all project types.
+/// Projects are traversed in parallel, so they have to be sendable across threads.
+pub trait Project: Any + Send {
     /// Gets the root path of the project
     fn get_root(&self) -> &PathBuf;
 
     /// Gets the name of the project
     fn get_name(&self) -> &str;
 
-    /// Gets the component graph of the project
+    /// Gets the components found while traversing the project
     fn get_component_graph(&self) -> &ComponentRegistry;
 
     /// Gets mutable access to the component graph
@@ -35,26 +41,256 @@ pub trait Project: Any {
     /// Finds a specific dependency by name
     fn find_dependency(&self, name: &str) -> Option<String>;
 
-    /// Traverses the project to analyze its components
-    fn traverse(&mut self, exclude: &Vec<String>, include: &Vec<String>);
+    /// Traverses the project to analyze its components.
+    /// `registry` holds the components of the projects that were traversed before,
+    /// the components of this project are collected in its own component graph.
+    fn traverse(&mut self, registry: &ComponentRegistry, exclude: &[String], include: &[String]);
 
     /// Returns a reference to this object as an Any trait object
     fn as_any(&self) -> &dyn Any;
 }
 
+/// Marks the components that are reachable from the entry points of a project.
+/// Components defined or rendered in an entry point are the roots of the analysis,
+/// components exported from an entry point form the public API of the project.
+fn apply_entry_points(
+    registry: &mut ComponentRegistry,
+    resolver: &ProjectResolver,
+    project_root: &Path,
+    project_name: &str,
+    entry_points: &[PathBuf],
+) {
+    Logger::info("Analyzing entry points from config file");
+
+    // components are identified by their name and the path relative to the project
+    let to_id = |name: &str, path: &Path| {
+        path.starts_with(project_root).then(|| {
+            ComponentNode::new(
+                name.to_string(),
+                replace_absolute_path_with_project_name(
+                    project_root.to_path_buf(),
+                    path.to_path_buf(),
+                    project_name.to_string(),
+                ),
+                BTreeMap::new(),
+            )
+            .id
+        })
+    };
+
+    let mut root_ids = Vec::new();
+    let mut public_ids = Vec::new();
+
+    for entry_point in entry_points {
+        if !entry_point.is_file() {
+            Logger::warn(&format!(
+                "Entry point {} does not exist",
+                entry_point.display()
+            ));
+            continue;
+        }
+
+        let entry_path = replace_absolute_path_with_project_name(
+            project_root.to_path_buf(),
+            entry_point.clone(),
+            project_name.to_string(),
+        );
+        root_ids.extend(
+            registry
+                .get_project_components(project_name)
+                .into_iter()
+                .filter(|info| info.node.file_path == entry_path)
+                .map(|info| info.node.id.clone()),
+        );
+
+        let entry_components = find_entry_components(resolver, entry_point);
+        root_ids.extend(
+            entry_components
+                .rendered
+                .iter()
+                .filter_map(|(name, path)| to_id(name, path)),
+        );
+        public_ids.extend(
+            entry_components
+                .exported
+                .iter()
+                .filter_map(|(name, path)| to_id(name, path))
+                .filter(|id| registry.get_component(id).is_some()),
+        );
+    }
+
+    registry.set_entry_points(project_name, &root_ids, &public_ids);
+}
+
+/// Counts the elements a component renders by their source for the adoption of its project.
+/// Elements are grouped by the directory of the file the component is defined in.
+/// Children imported from one of the `source_projects` count as workspace components.
+fn add_adoption(
+    registry: &mut ComponentRegistry,
+    project_name: &str,
+    source_projects: &[String],
+    file_path: &Path,
+    component: &Component,
+) {
+    let directory = file_path.parent().unwrap_or(file_path);
+    let adoption = registry.get_adoption_mut(project_name);
+
+    adoption.add_intrinsic_elements(directory, &component.intrinsic_elements);
+    for child in &component.children {
+        let is_source_project = source_projects.iter().any(|name| {
+            child.project_name.as_ref() == Some(name) || child.origin_file_path.starts_with(name)
+        });
+        let source = if is_source_project {
+            ElementSource::Workspace
+        } else {
+            child.source(project_name)
+        };
+        adoption.add(directory, source, 1);
+    }
+}
+
+/// Collects the files of a walker, sorted by path so results don't depend on the walk order
+fn collect_files(walker: ignore::Walk) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walker
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry.into_path()),
+            Err(e) => {
+                Logger::error(&format!("Error while walking file: {}", e));
+                None
+            }
+        })
+        .filter(|path| path.is_file() && is_supported_file(path))
+        .collect();
+    files.sort();
+
+    files
+}
+
+/// Parses and analyzes files in parallel.
+/// The components of every file are returned in the order of the given files.
+/// Files that didn't change since the cache was written are not analyzed again.
+fn analyze_files(
+    files: &[PathBuf],
+    resolver: &ProjectResolver,
+    package_resolver: &PackageResolver,
+    cache: Option<&mut AnalysisCache>,
+) -> Vec<(PathBuf, Vec<Component>)> {
+    let Some(cache) = cache else {
+        return files
+            .par_iter()
+            .map(|path| {
+                Logger::debug(&format!("Analyzing file: {}", path.display()), 2);
+                (path.clone(), analyze_file(path, resolver, package_resolver))
+            })
+            .collect();
+    };
+
+    let hashes: HashMap<PathBuf, String> = files
+        .par_iter()
+        .filter_map(|path| hash_file(path).map(|hash| (path.clone(), hash)))
+        .collect();
+
+    let stored = &*cache;
+    // the dependencies are only recorded for files that were analyzed
+    let results: Vec<(PathBuf, Vec<Component>, Option<Vec<SourceDependency>>)> = files
+        .par_iter()
+        .map(|path| {
+            let cached = hashes
+                .get(path)
+                .and_then(|hash| stored.get(path, hash, &hashes, resolver));
+
+            match cached {
+                Some(components) => (path.clone(), components, None),
+                None => {
+                    Logger::debug(&format!("Analyzing file: {}", path.display()), 2);
+                    let file_resolver = resolver.for_file();
+                    let components = analyze_file(path, &file_resolver, package_resolver);
+                    (path.clone(), components, Some(file_resolver.dependencies()))
+                }
+            }
+        })
+        .collect();
+
+    let mut cache_hits = 0;
+    for (path, components, dependencies) in &results {
+        match (dependencies, hashes.get(path)) {
+            (None, _) => cache_hits += 1,
+            (Some(dependencies), Some(hash)) => cache.insert(
+                path.clone(),
+                hash.clone(),
+                components.clone(),
+                dependencies,
+                &hashes,
+            ),
+            (Some(_), None) => {}
+        }
+    }
+    cache.retain(files);
+
+    Logger::debug(
+        &format!("Reused {} of {} files from cache", cache_hits, files.len()),
+        1,
+    );
+
+    results
+        .into_iter()
+        .map(|(path, components, _)| (path, components))
+        .collect()
+}
+
+/// Parses a file and extracts its components
+fn analyze_file(
+    path: &PathBuf,
+    resolver: &ProjectResolver,
+    package_resolver: &PackageResolver,
+) -> Vec<Component> {
+    let file_content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            Logger::error(&format!("Failed to read file {}: {}", path.display(), e));
+            return Vec::new();
+        }
+    };
+
+    let allocator = oxc_allocator::Allocator::default();
+    let (_parser_return, semantic_return) = match parse_source(&allocator, path, &file_content) {
+        Ok(result) => result,
+        Err(e) => {
+            Logger::error(&format!("Failed to parse file {}: {}", path.display(), e));
+            return Vec::new();
+        }
+    };
+
+    // Create a ReactAnalyzer instance
+    let mut react_analyzer = ReactAnalyzer::new(
+        &semantic_return.semantic,
+        path.clone(),
+        resolver,
+        package_resolver,
+    );
+
+    // Extract components using the analyzer
+    react_analyzer.analyze()
+}
+
 /// A project that is used as a dependency by other projects
 #[derive(Clone)]
 pub struct SourceProject {
     pub project_root: PathBuf,
     pub project_name: String,
-    component_registry: *mut ComponentRegistry,
+    /// The components found while traversing the project
+    component_registry: ComponentRegistry,
     resolver: ProjectResolver,
     package_resolver: PackageResolver,
     config: Option<ConfigValues>,
+    /// Directory of the analysis cache, `None` if caching is disabled
+    cache_dir: Option<PathBuf>,
 }
 
 impl SourceProject {
-    pub fn new(project_root: PathBuf, component_registry: &mut ComponentRegistry) -> Self {
+    /// Creates a project that collects its components in its own registry.
+    /// The registry is merged into the workspace registry after [`Project::traverse`].
+    pub fn new(project_root: PathBuf) -> Self {
         if !project_root.exists() {
             panic!("Project root does not exist");
         }
@@ -67,7 +303,7 @@ impl SourceProject {
             .expect("Failed to read package.json");
 
         let project_name = package_json.name.unwrap_or_else(|| {
-            Logger::warn(&format!("No project name found in package.json"));
+            Logger::warn("No project name found in package.json");
             project_root.to_string_lossy().to_string()
         });
 
@@ -83,15 +319,22 @@ impl SourceProject {
         Self {
             project_root,
             project_name,
-            component_registry: component_registry as *mut ComponentRegistry,
+            component_registry: ComponentRegistry::new(),
             resolver,
             package_resolver: PackageResolver::new(),
             config,
+            cache_dir: None,
         }
     }
 
+    /// Enables the analysis cache in the given directory
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
     /// Builds a walker with correct overrides and patterns.
-    fn build_walker(&self, exclude: &Vec<String>, include: &Vec<String>) -> ignore::Walk {
+    fn build_walker(&self, exclude: &[String], include: &[String]) -> ignore::Walk {
         let exclude_patterns: Vec<String> = exclude
             .iter()
             .map(|pattern| format!("!{}", pattern)) // Add '!' to each pattern
@@ -116,48 +359,11 @@ impl SourceProject {
             .build()
     }
 
-    /// Analyzes a file and adds the found components to the component graph.
-    fn analyze_file(&mut self, path: &PathBuf) {
-        if !path.is_file() {
-            return;
-        }
-
-        let file_extension = path.extension().and_then(|ext| ext.to_str());
-        if file_extension != Some("tsx") && file_extension != Some("ts") {
-            return;
-        }
-
-        let file_content = match std::fs::read_to_string(path) {
-            Ok(content) => content,
-            Err(e) => {
-                Logger::error(&format!("Failed to read file {}: {}", path.display(), e));
-                return;
-            }
-        };
-
-        let allocator = oxc_allocator::Allocator::default();
-        let (parser_return, semantic_return) = match parse_tsx(&allocator, path, &file_content) {
-            Ok(result) => result,
-            Err(e) => {
-                Logger::error(&format!("Failed to parse file {}: {}", path.display(), e));
-                return;
-            }
-        };
-
-        // Create a ReactAnalyzer instance
-        let mut react_analyzer = ReactAnalyzer::new(
-            &semantic_return.semantic,
-            path.clone(),
-            &self.resolver,
-            &self.package_resolver,
-        );
-
-        // Extract components using the analyzer
-        let components = react_analyzer.analyze();
-
+    /// Adds the components found in a file to the component graph.
+    fn add_components(&mut self, components: Vec<Component>) {
         for component in components {
             // Create base component with props
-            let base_component = ComponentNode::new(
+            let mut base_component = ComponentNode::new(
                 component.name.clone(),
                 replace_absolute_path_with_project_name(
                     self.project_root.clone(),
@@ -166,56 +372,88 @@ impl SourceProject {
                 ),
                 component.props.clone(),
             );
+            base_component.declared_props = component.declared_props.clone();
+            base_component.kind = component.kind;
+            base_component.wrappers = component.wrappers.clone();
+            base_component.location = Some(component.location);
+            base_component.intrinsic_elements = component.intrinsic_elements.clone();
+            base_component.intrinsic_element_locations =
+                component.intrinsic_element_locations.clone();
+
+            add_adoption(
+                &mut self.component_registry,
+                &self.project_name,
+                &[],
+                &base_component.file_path,
+                &component,
+            );
 
             // Create child components
-            let child_components: Vec<ComponentNode> = component
+            let child_components: Vec<(ComponentNode, ComponentUsage)> = component
                 .children
                 .into_iter()
                 .map(|child| {
-                    ComponentNode::new(
-                        child.name,
+                    let usage = child.usage();
+                    let mut node = ComponentNode::new(
+                        child.component_name().to_string(),
                         replace_absolute_path_with_project_name(
                             self.project_root.clone(),
                             child.origin_file_path.clone(),
                             self.project_name.clone(),
                         ),
                         child.props,
-                    )
+                    );
+                    node.prop_values = child.prop_values;
+                    node.package_version = child.package_version;
+
+                    (node, usage)
                 })
                 .collect();
 
             // Add everything to the graph in one operation
-            unsafe {
-                if let Some(existing) = (*self.component_registry)
-                    .find_component(&base_component.name, &self.project_name)
+            if let Some(existing_id) = self
+                .component_registry
+                .get_component(&base_component.id)
+                .map(|info| info.node.id.clone())
+            {
+                self.component_registry
+                    .add_props(&existing_id, &base_component.props);
+                self.component_registry
+                    .set_definition(&existing_id, &base_component);
+            } else {
+                self.component_registry
+                    .add_component(base_component.clone(), self.project_name.clone());
+            }
+
+            for (child, usage) in child_components {
+                if let Some(existing_child_id) = self
+                    .component_registry
+                    .get_component(&child.id)
+                    .map(|info| info.node.id.clone())
                 {
-                    (*self.component_registry)
-                        .add_props(&existing.node.id, &base_component.props);
+                    self.component_registry
+                        .add_props(&existing_child_id, &child.props);
+                    self.component_registry
+                        .add_prop_values(&existing_child_id, &child.prop_values);
                 } else {
-                    (*self.component_registry)
-                        .add_component(base_component.clone(), self.project_name.clone());
-                }
-
-                for child in child_components {
-                    if let Some(existing_child) = (*self.component_registry)
-                        .find_component(&child.name, &self.project_name)
-                    {
-                        (*self.component_registry)
-                            .add_props(&existing_child.node.id, &child.props);
-                    } else {
-                        (*self.component_registry)
-                            .add_component(child.clone(), self.project_name.clone());
-                    }
-                    (*self.component_registry)
-                        .add_dependency(
-                            &base_component.id,
-                            &child.id,
-                            Some(self.project_name.clone()),
-                        )
-                        .unwrap_or_else(|e| {
-                            Logger::error(&format!("Failed to add dependency: {}", e));
-                        });
+                    self.component_registry
+                        .add_component(child.clone(), self.project_name.clone());
                 }
+                self.component_registry
+                    .add_dependency(
+                        &base_component.id,
+                        &child.id,
+                        Some(self.project_name.clone()),
+                    )
+                    .unwrap_or_else(|e| {
+                        Logger::error(&format!("Failed to add dependency: {}", e));
+                    });
+                self.component_registry.add_instance(
+                    &base_component.id,
+                    &child.id,
+                    usage,
+                    &child.props,
+                );
             }
         }
     }
@@ -231,11 +469,11 @@ impl Project for SourceProject {
     }
 
     fn get_component_graph(&self) -> &ComponentRegistry {
-        unsafe { &*self.component_registry }
+        &self.component_registry
     }
 
     fn get_component_graph_mut(&mut self) -> &mut ComponentRegistry {
-        unsafe { &mut *self.component_registry }
+        &mut self.component_registry
     }
 
     fn get_dependencies(&self) -> Option<HashSet<String>> {
@@ -248,9 +486,11 @@ impl Project for SourceProject {
             .and_then(|package_json| package_json.find_dependency(name))
     }
 
-    fn traverse(&mut self, exclude: &Vec<String>, include: &Vec<String>) {
-        let mut exclude_patterns = exclude.clone();
-        let mut include_patterns = include.clone();
+    fn traverse(&mut self, _registry: &ComponentRegistry, exclude: &[String], include: &[String]) {
+        self.component_registry = ComponentRegistry::new();
+
+        let mut exclude_patterns = exclude.to_vec();
+        let mut include_patterns = include.to_vec();
 
         // Merge config values with CLI values
         if let Some(config) = &self.config {
@@ -260,35 +500,52 @@ impl Project for SourceProject {
             if let Some(config_include) = &config.include {
                 include_patterns.extend(config_include.clone());
             }
-            // Handle entry points from config
-            if let Some(config_entry_points) = &config.entry_points {
-                Logger::info("Analyzing entry points from config file");
-                let entry_points = config_entry_points
+        }
+
+        let entry_points = self
+            .config
+            .as_ref()
+            .and_then(|config| config.entry_points.clone())
+            .map(|entry_points| {
+                entry_points
                     .iter()
                     .map(|path| self.project_root.join(path))
-                    .collect::<Vec<_>>();
-            }
-        }
+                    .collect::<Vec<_>>()
+            });
 
         Logger::info(&format!(
             "Starting traversal of source project: {}",
             self.project_name
         ));
 
-        let walker = self.build_walker(&exclude_patterns, &include_patterns);
+        let files = collect_files(self.build_walker(&exclude_patterns, &include_patterns));
 
-        for entry in walker {
-            match entry {
-                Ok(entry) => {
-                    let path = entry.path().to_path_buf();
+        // Files are parsed in parallel, their components are added in the order of the files
+        let mut cache = self.cache_dir.as_ref().map(|cache_dir| {
+            AnalysisCache::load(cache_dir, &self.project_name, &self.project_root)
+        });
+        let analyzed_files = analyze_files(
+            &files,
+            &self.resolver,
+            &self.package_resolver,
+            cache.as_mut(),
+        );
+        if let Some(cache) = cache {
+            cache.save();
+        }
 
-                    if path.is_file() {
-                        Logger::debug(&format!("Analyzing file: {}", path.display()), 2);
-                        self.analyze_file(&path);
-                    }
-                }
-                Err(e) => Logger::error(&format!("Error while walking file: {}", e)),
-            }
+        for (_, components) in analyzed_files {
+            self.add_components(components);
+        }
+
+        if let Some(entry_points) = entry_points {
+            apply_entry_points(
+                &mut self.component_registry,
+                &self.resolver,
+                &self.project_root,
+                &self.project_name,
+                &entry_points,
+            );
         }
     }
 
@@ -302,15 +559,21 @@ impl Project for SourceProject {
 pub struct ConsumerProject {
     pub project_root: PathBuf,
     pub project_name: String,
-    component_registry: *mut ComponentRegistry,
+    /// The components found while traversing the project
+    component_registry: ComponentRegistry,
     resolver: ProjectResolver,
     package_resolver: PackageResolver,
     config: Option<ConfigValues>,
+    /// Directory of the analysis cache, `None` if caching is disabled
+    cache_dir: Option<PathBuf>,
     source_projects: Vec<SourceProject>,
 }
 
 impl ConsumerProject {
-    pub fn new(project_root: PathBuf, component_registry: &mut ComponentRegistry) -> Self {
+    /// Creates a project that collects its components in its own registry.
+    /// The components of its source projects are read from the registry passed to
+    /// [`Project::traverse`].
+    pub fn new(project_root: PathBuf) -> Self {
         if !project_root.exists() {
             panic!("Project root does not exist");
         }
@@ -323,7 +586,7 @@ impl ConsumerProject {
             .expect("Failed to read package.json");
 
         let project_name = package_json.name.unwrap_or_else(|| {
-            Logger::warn(&format!("No project name found in package.json"));
+            Logger::warn("No project name found in package.json");
             project_root.to_string_lossy().to_string()
         });
 
@@ -339,10 +602,11 @@ impl ConsumerProject {
         Self {
             project_root,
             project_name,
-            component_registry: component_registry as *mut ComponentRegistry,
+            component_registry: ComponentRegistry::new(),
             resolver,
             package_resolver: PackageResolver::new(),
             config,
+            cache_dir: None,
             source_projects: Vec::new(),
         }
     }
@@ -355,8 +619,31 @@ impl ConsumerProject {
         &self.source_projects
     }
 
+    /// Adds the components of the source projects so usages can be linked to them.
+    /// Only the identity of a component is copied, everything found in this project
+    /// is added on top when the results are merged into the workspace.
+    fn add_source_components(&mut self, registry: &ComponentRegistry) {
+        for source_project in &self.source_projects {
+            for info in registry.get_project_components(&source_project.project_name) {
+                let node = ComponentNode::new(
+                    info.node.name.clone(),
+                    info.node.file_path.clone(),
+                    BTreeMap::new(),
+                );
+                self.component_registry
+                    .add_component(node, source_project.project_name.clone());
+            }
+        }
+    }
+
+    /// Enables the analysis cache in the given directory
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
     /// Builds a walker with correct overrides and patterns.
-    fn build_walker(&self, exclude: &Vec<String>, include: &Vec<String>) -> ignore::Walk {
+    fn build_walker(&self, exclude: &[String], include: &[String]) -> ignore::Walk {
         let exclude_patterns: Vec<String> = exclude
             .iter()
             .map(|pattern| format!("!{}", pattern)) // Add '!' to each pattern
@@ -381,45 +668,29 @@ impl ConsumerProject {
             .build()
     }
 
-    /// Analyzes a file and adds the found components to the component graph.
-    fn analyze_file(&mut self, path: &PathBuf) {
-        if !path.is_file() {
-            return;
-        }
-
-        let file_extension = path.extension().and_then(|ext| ext.to_str());
-        if file_extension != Some("tsx") && file_extension != Some("ts") {
-            return;
-        }
-
-        let file_content = match std::fs::read_to_string(path) {
-            Ok(content) => content,
-            Err(e) => {
-                Logger::error(&format!("Failed to read file {}: {}", path.display(), e));
-                return;
-            }
-        };
-
-        let allocator = oxc_allocator::Allocator::default();
-        let (parser_return, semantic_return) = match parse_tsx(&allocator, path, &file_content) {
-            Ok(result) => result,
-            Err(e) => {
-                Logger::error(&format!("Failed to parse file {}: {}", path.display(), e));
-                return;
-            }
+    /// Finds the source project a child component was imported from.
+    /// Resolved paths are matched by their real path against the project roots, so packages
+    /// linked into `node_modules` are found. Unresolved imports are matched by the package name.
+    fn find_source_project(&self, origin_file_path: &Path) -> Option<String> {
+        let source_project = if origin_file_path.is_absolute() {
+            let origin_file_path = origin_file_path.canonicalize().ok()?;
+            self.source_projects.iter().find(|source_project| {
+                source_project
+                    .project_root
+                    .canonicalize()
+                    .is_ok_and(|root| origin_file_path.starts_with(root))
+            })
+        } else {
+            self.source_projects
+                .iter()
+                .find(|source_project| origin_file_path.starts_with(&source_project.project_name))
         };
 
-        // Create a ReactAnalyzer instance
-        let mut react_analyzer = ReactAnalyzer::new(
-            &semantic_return.semantic,
-            path.clone(),
-            &self.resolver,
-            &self.package_resolver,
-        );
-
-        // Extract components using the analyzer
-        let components = react_analyzer.analyze();
+        source_project.map(|source_project| source_project.project_name.clone())
+    }
 
+    /// Adds the components found in a file to the component graph.
+    fn add_components(&mut self, path: &Path, components: Vec<Component>) {
         for component in components {
             Logger::debug(&format!("component: {}", component.name), 1);
             // Check if this component is from a source project
@@ -441,49 +712,59 @@ impl ConsumerProject {
                 // Don't register the component again, just create dependencies
                 if let Some(source_project_name) = source_project_name {
                     // Find the component in the source project
-                    if let Some(source_component) = unsafe {
-                        (*self.component_registry)
-                            .find_component(&component.name, &source_project_name)
-                    } {
+                    if let Some(source_component_id) = self
+                        .component_registry
+                        .find_component(&component.name, &source_project_name)
+                        .map(|info| info.node.id.clone())
+                    {
                         // Create child components
-                        let child_components: Vec<ComponentNode> = component
+                        let child_components: Vec<(ComponentNode, ComponentUsage)> = component
                             .children
                             .into_iter()
                             .map(|child| {
-                                ComponentNode::new(
-                                    child.name,
+                                let usage = child.usage();
+                                let mut node = ComponentNode::new(
+                                    child.component_name().to_string(),
                                     replace_absolute_path_with_project_name(
                                         self.project_root.clone(),
                                         child.origin_file_path.clone(),
                                         self.project_name.clone(),
                                     ),
                                     child.props,
-                                )
+                                );
+                                node.prop_values = child.prop_values;
+                                node.package_version = child.package_version;
+
+                                (node, usage)
                             })
                             .collect();
 
                         // Add dependencies for each child component
-                        for child in child_components {
-                            if let Some(child_component) = unsafe {
-                                (*self.component_registry)
-                                    .find_component(&child.name, &source_project_name)
-                            } {
-                                unsafe {
-                                    (*self.component_registry)
-                                        .add_props(&child_component.node.id, &child.props);
-                                    (*self.component_registry)
-                                        .add_dependency(
-                                            &source_component.node.id,
-                                            &child_component.node.id,
-                                            Some(source_project_name.clone()),
-                                        )
-                                        .unwrap_or_else(|e| {
-                                            Logger::error(&format!(
-                                                "Failed to add dependency: {}",
-                                                e
-                                            ));
-                                        });
-                                }
+                        for (child, usage) in child_components {
+                            if let Some(child_component_id) = self
+                                .component_registry
+                                .find_component(&child.name, &source_project_name)
+                                .map(|info| info.node.id.clone())
+                            {
+                                self.component_registry
+                                    .add_props(&child_component_id, &child.props);
+                                self.component_registry
+                                    .add_prop_values(&child_component_id, &child.prop_values);
+                                self.component_registry
+                                    .add_dependency(
+                                        &source_component_id,
+                                        &child_component_id,
+                                        Some(source_project_name.clone()),
+                                    )
+                                    .unwrap_or_else(|e| {
+                                        Logger::error(&format!("Failed to add dependency: {}", e));
+                                    });
+                                self.component_registry.add_instance(
+                                    &source_component_id,
+                                    &child_component_id,
+                                    usage,
+                                    &child.props,
+                                );
                             }
                         }
                     }
@@ -491,7 +772,7 @@ impl ConsumerProject {
             } else {
                 // This is a component defined in the consumer project
                 // Create base component with props
-                let base_component = ComponentNode::new(
+                let mut base_component = ComponentNode::new(
                     component.name.clone(),
                     replace_absolute_path_with_project_name(
                         self.project_root.clone(),
@@ -500,107 +781,131 @@ impl ConsumerProject {
                     ),
                     component.props.clone(),
                 );
+                base_component.declared_props = component.declared_props.clone();
+                base_component.kind = component.kind;
+                base_component.wrappers = component.wrappers.clone();
+                base_component.location = Some(component.location);
+                base_component.intrinsic_elements = component.intrinsic_elements.clone();
+                base_component.intrinsic_element_locations =
+                    component.intrinsic_element_locations.clone();
+
+                let source_project_names: Vec<String> = self
+                    .source_projects
+                    .iter()
+                    .map(|project| project.project_name.clone())
+                    .collect();
+                add_adoption(
+                    &mut self.component_registry,
+                    &self.project_name,
+                    &source_project_names,
+                    &base_component.file_path,
+                    &component,
+                );
 
                 Logger::debug(&format!("child_components: {:?}", component.children), 2);
 
                 // Create child components
-                let child_components: Vec<ComponentNode> = component
+                let child_components: Vec<(ComponentNode, ComponentUsage, PathBuf)> = component
                     .children
                     .into_iter()
                     .map(|child| {
-                        ComponentNode::new(
-                            child.name,
+                        let usage = child.usage();
+                        let mut node = ComponentNode::new(
+                            child.component_name().to_string(),
                             replace_absolute_path_with_project_name(
                                 self.project_root.clone(),
                                 child.origin_file_path.clone(),
                                 self.project_name.clone(),
                             ),
                             child.props,
-                        )
+                        );
+                        node.prop_values = child.prop_values;
+                        node.package_version = child.package_version;
+
+                        (node, usage, child.origin_file_path)
                     })
                     .collect();
 
                 // Add everything to the graph in one operation
-                unsafe {
-                    if let Some(existing) = (*self.component_registry)
-                        .find_component(&base_component.name, &self.project_name)
-                    {
-                        (*self.component_registry)
-                            .add_props(&existing.node.id, &base_component.props);
-                    } else {
-                        (*self.component_registry)
-                            .add_component(base_component.clone(), self.project_name.clone());
-                    }
-
-                    for child in child_components {
-                        Logger::debug(&format!("child: {}", child.name), 2);
-                        // Check if the child component is from a source project
-                        let mut child_is_from_source = false;
-                        let mut child_source_project_name = None;
-
-                        for source_project in &self.source_projects {
-                            // Extract project name from the file path
-                            if let Some(path_str) = child.file_path.to_str() {
-                                if path_str.starts_with(&source_project.project_name) {
-                                    child_is_from_source = true;
-                                    child_source_project_name =
-                                        Some(source_project.project_name.clone());
-                                    break;
-                                }
-                            }
-                        }
+                if let Some(existing_id) = self
+                    .component_registry
+                    .get_component(&base_component.id)
+                    .map(|info| info.node.id.clone())
+                {
+                    self.component_registry
+                        .add_props(&existing_id, &base_component.props);
+                    self.component_registry
+                        .set_definition(&existing_id, &base_component);
+                } else {
+                    self.component_registry
+                        .add_component(base_component.clone(), self.project_name.clone());
+                }
 
-                        if child_is_from_source {
-                            // Find the component in the source project
-                            if let Some(child_source_project_name) = child_source_project_name {
-                                if let Some(source_component) = unsafe {
-                                    (*self.component_registry)
-                                        .find_component(&child.name, &child_source_project_name)
-                                } {
-                                    (*self.component_registry)
-                                        .add_props(&source_component.node.id, &child.props);
-                                    unsafe {
-                                        (*self.component_registry)
-                                            .add_dependency(
-                                                &base_component.id,
-                                                &source_component.node.id,
-                                                Some(child_source_project_name),
-                                            )
-                                                .unwrap_or_else(|e| {
-                                                Logger::error(&format!(
-                                                    "Failed to add dependency: {}",
-                                                    e
-                                                ));
-                                            });
-                                    }
-                                } else {
-                                    Logger::error(&format!(
-                                        "Could not find component {} in source project {}",
-                                        child.name, child_source_project_name
-                                    ));
-                                }
-                            }
-                        } else {
-                            // Register the child component and add dependency
-                            if let Some(existing_child) = (*self.component_registry)
-                                .find_component(&child.name, &self.project_name)
-                            {
-                                (*self.component_registry)
-                                    .add_props(&existing_child.node.id, &child.props);
-                            } else {
-                                (*self.component_registry)
-                                    .add_component(child.clone(), self.project_name.clone());
-                            }
-                            (*self.component_registry)
+                for (child, usage, origin) in child_components {
+                    Logger::debug(&format!("child: {}", child.name), 2);
+                    // Check if the child component is from a source project
+                    if let Some(child_source_project_name) = self.find_source_project(&origin) {
+                        // Find the component in the source project
+                        if let Some(source_component_id) = self
+                            .component_registry
+                            .find_component(&child.name, &child_source_project_name)
+                            .map(|info| info.node.id.clone())
+                        {
+                            self.component_registry
+                                .add_props(&source_component_id, &child.props);
+                            self.component_registry
+                                .add_prop_values(&source_component_id, &child.prop_values);
+                            self.component_registry
                                 .add_dependency(
                                     &base_component.id,
-                                    &child.id,
-                                    Some(self.project_name.clone()),
+                                    &source_component_id,
+                                    Some(child_source_project_name),
                                 )
                                 .unwrap_or_else(|e| {
                                     Logger::error(&format!("Failed to add dependency: {}", e));
                                 });
+                            self.component_registry.add_instance(
+                                &base_component.id,
+                                &source_component_id,
+                                usage,
+                                &child.props,
+                            );
+                        } else {
+                            Logger::error(&format!(
+                                "Could not find component {} in source project {}",
+                                child.name, child_source_project_name
+                            ));
                         }
+                    } else {
+                        // Register the child component and add dependency
+                        if let Some(existing_child_id) = self
+                            .component_registry
+                            .get_component(&child.id)
+                            .map(|info| info.node.id.clone())
+                        {
+                            self.component_registry
+                                .add_props(&existing_child_id, &child.props);
+                            self.component_registry
+                                .add_prop_values(&existing_child_id, &child.prop_values);
+                        } else {
+                            self.component_registry
+                                .add_component(child.clone(), self.project_name.clone());
+                        }
+                        self.component_registry
+                            .add_dependency(
+                                &base_component.id,
+                                &child.id,
+                                Some(self.project_name.clone()),
+                            )
+                            .unwrap_or_else(|e| {
+                                Logger::error(&format!("Failed to add dependency: {}", e));
+                            });
+                        self.component_registry.add_instance(
+                            &base_component.id,
+                            &child.id,
+                            usage,
+                            &child.props,
+                        );
                     }
                 }
             }
@@ -618,11 +923,11 @@ impl Project for ConsumerProject {
     }
 
     fn get_component_graph(&self) -> &ComponentRegistry {
-        unsafe { &*self.component_registry }
+        &self.component_registry
     }
 
     fn get_component_graph_mut(&mut self) -> &mut ComponentRegistry {
-        unsafe { &mut *self.component_registry }
+        &mut self.component_registry
     }
 
     fn get_dependencies(&self) -> Option<HashSet<String>> {
@@ -635,9 +940,12 @@ impl Project for ConsumerProject {
             .and_then(|package_json| package_json.find_dependency(name))
     }
 
-    fn traverse(&mut self, exclude: &Vec<String>, include: &Vec<String>) {
-        let mut exclude_patterns = exclude.clone();
-        let mut include_patterns = include.clone();
+    fn traverse(&mut self, registry: &ComponentRegistry, exclude: &[String], include: &[String]) {
+        self.component_registry = ComponentRegistry::new();
+        self.add_source_components(registry);
+
+        let mut exclude_patterns = exclude.to_vec();
+        let mut include_patterns = include.to_vec();
 
         // Merge config values with CLI values
         if let Some(config) = &self.config {
@@ -647,35 +955,52 @@ impl Project for ConsumerProject {
             if let Some(config_include) = &config.include {
                 include_patterns.extend(config_include.clone());
             }
-            // Handle entry points from config
-            if let Some(config_entry_points) = &config.entry_points {
-                Logger::info("Analyzing entry points from config file");
-                let entry_points = config_entry_points
+        }
+
+        let entry_points = self
+            .config
+            .as_ref()
+            .and_then(|config| config.entry_points.clone())
+            .map(|entry_points| {
+                entry_points
                     .iter()
                     .map(|path| self.project_root.join(path))
-                    .collect::<Vec<_>>();
-            }
-        }
+                    .collect::<Vec<_>>()
+            });
 
         Logger::info(&format!(
             "Starting traversal of consumer project: {}",
             self.project_name
         ));
 
-        let walker = self.build_walker(&exclude_patterns, &include_patterns);
+        let files = collect_files(self.build_walker(&exclude_patterns, &include_patterns));
 
-        for entry in walker {
-            match entry {
-                Ok(entry) => {
-                    let path = entry.path().to_path_buf();
+        // Files are parsed in parallel, their components are added in the order of the files
+        let mut cache = self.cache_dir.as_ref().map(|cache_dir| {
+            AnalysisCache::load(cache_dir, &self.project_name, &self.project_root)
+        });
+        let analyzed_files = analyze_files(
+            &files,
+            &self.resolver,
+            &self.package_resolver,
+            cache.as_mut(),
+        );
+        if let Some(cache) = cache {
+            cache.save();
+        }
 
-                    if path.is_file() {
-                        Logger::debug(&format!("Analyzing file: {}", path.display()), 2);
-                        self.analyze_file(&path);
-                    }
-                }
-                Err(e) => Logger::error(&format!("Error while walking file: {}", e)),
-            }
+        for (path, components) in analyzed_files {
+            self.add_components(&path, components);
+        }
+
+        if let Some(entry_points) = entry_points {
+            apply_entry_points(
+                &mut self.component_registry,
+                &self.resolver,
+                &self.project_root,
+                &self.project_name,
+                &entry_points,
+            );
         }
     }
 
@@ -1,36 +1,38 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
 use oxc_resolver::{Resolution, ResolveOptions, Resolver, TsconfigOptions, TsconfigReferences};
 
+use crate::analyze::react::{ComponentRoot, ModuleIndex};
+
+/// Something the components of a file depend on besides the file itself
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum SourceDependency {
+    /// A module or `package.json` read while resolving components
+    File(PathBuf),
+    /// A specifier that could not be resolved from a directory
+    Unresolved {
+        directory: PathBuf,
+        specifier: String,
+    },
+}
+
+/// Resolves import specifiers of a project.
+/// Clones share the same resolver and module index, so resolutions and parsed modules are reused.
 #[derive(Clone)]
 pub struct ProjectResolver {
-    tsconfig_path: Option<PathBuf>,
+    resolver: Arc<Resolver>,
+    modules: Arc<ModuleIndex>,
+    /// The dependencies recorded while analyzing a single file, see [`ProjectResolver::for_file`]
+    dependencies: Option<Arc<Mutex<Vec<SourceDependency>>>>,
 }
 
 impl ProjectResolver {
     pub fn new(tsconfig_path: Option<PathBuf>) -> Self {
-        Self { tsconfig_path }
-    }
-
-    /// resolve a relative file path to an absolute file path
-    ///
-    /// dir: is the directory of the file that has the import statement
-    /// specifier: is the relative file path that the import statement is importing
-    /// tsconfig: is the path to the tsconfig.json file that contains the tsconfig options and tsconfigPaths
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use std::path::PathBuf;
-    /// use spinne_core::ProjectResolver;
-    ///
-    /// let dir = PathBuf::from("/Users/tim/projects/spinne/src/index.ts");
-    /// let resolver = ProjectResolver::new(None);
-    /// let resolution = resolver.resolve(&dir, "./components/Button");
-    /// ```
-    pub fn resolve(&self, dir: &PathBuf, specifier: &str) -> Result<Resolution, String> {
         let options = ResolveOptions {
-            tsconfig: self.tsconfig_path.as_ref().map(|tsconfig| TsconfigOptions {
+            tsconfig: tsconfig_path.as_ref().map(|tsconfig| TsconfigOptions {
                 config_file: tsconfig.to_path_buf(),
                 references: TsconfigReferences::Auto,
             }),
@@ -49,6 +51,8 @@ impl ProjectResolver {
             extensions: vec![
                 ".ts".to_string(),
                 ".tsx".to_string(),
+                ".mts".to_string(),
+                ".cts".to_string(),
                 ".d.ts".to_string(),
                 ".js".to_string(),
                 ".jsx".to_string(),
@@ -92,9 +96,123 @@ impl ProjectResolver {
             ..ResolveOptions::default()
         };
 
-        match Resolver::new(options).resolve(dir, &specifier) {
-            Ok(resolved_path) => Ok(resolved_path),
-            Err(e) => Err(e.to_string()),
+        Self {
+            resolver: Arc::new(Resolver::new(options)),
+            modules: Arc::new(ModuleIndex::default()),
+            dependencies: None,
+        }
+    }
+
+    /// Creates a resolver sharing this one's caches that records every file read and every
+    /// specifier that could not be resolved, so cached results of a file can be invalidated
+    pub(crate) fn for_file(&self) -> Self {
+        Self {
+            dependencies: Some(Arc::default()),
+            ..self.clone()
+        }
+    }
+
+    /// The dependencies recorded since the resolver was created with [`ProjectResolver::for_file`]
+    pub(crate) fn dependencies(&self) -> Vec<SourceDependency> {
+        let mut dependencies = self
+            .dependencies
+            .as_ref()
+            .map(|dependencies| dependencies.lock().unwrap().clone())
+            .unwrap_or_default();
+        dependencies.sort();
+        dependencies.dedup();
+
+        dependencies
+    }
+
+    /// Records a file the components of the current file depend on
+    pub(crate) fn record_file(&self, path: &Path) {
+        self.record(SourceDependency::File(path.to_path_buf()));
+    }
+
+    fn record(&self, dependency: SourceDependency) {
+        if let Some(dependencies) = &self.dependencies {
+            dependencies.lock().unwrap().push(dependency);
+        }
+    }
+
+    /// The modules parsed while resolving components of the project
+    pub(crate) fn modules(&self) -> &ModuleIndex {
+        &self.modules
+    }
+
+    /// Gets the root of a component exported from a module, computing it if it wasn't found before.
+    /// The dependencies recorded while computing a root are recorded again whenever it is reused.
+    pub(crate) fn get_root(
+        &self,
+        path: &Path,
+        component_name: &str,
+        find: impl FnOnce() -> Option<ComponentRoot>,
+    ) -> Option<ComponentRoot> {
+        match (
+            self.modules.get_root(path, component_name),
+            &self.dependencies,
+        ) {
+            (Some((root, _)), None) => return root,
+            (Some((root, Some(recorded))), Some(dependencies)) => {
+                dependencies
+                    .lock()
+                    .unwrap()
+                    .extend(recorded.iter().cloned());
+                return root;
+            }
+            // roots found without recording dependencies are computed again
+            _ => {}
+        }
+
+        let start = self
+            .dependencies
+            .as_ref()
+            .map(|dependencies| dependencies.lock().unwrap().len());
+        let root = find();
+        let recorded = self
+            .dependencies
+            .as_ref()
+            .zip(start)
+            .map(|(dependencies, start)| dependencies.lock().unwrap()[start..].into());
+        self.modules
+            .insert_root(path, component_name, root.clone(), recorded);
+
+        root
+    }
+
+    /// resolve a relative file path to an absolute file path
+    ///
+    /// dir: is the directory of the file that has the import statement
+    /// specifier: is the relative file path that the import statement is importing
+    /// tsconfig: is the path to the tsconfig.json file that contains the tsconfig options and tsconfigPaths
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use spinne_core::ProjectResolver;
+    ///
+    /// let dir = PathBuf::from("/Users/tim/projects/spinne/src/index.ts");
+    /// let resolver = ProjectResolver::new(None);
+    /// let resolution = resolver.resolve(&dir, "./components/Button");
+    /// ```
+    pub fn resolve(&self, dir: &PathBuf, specifier: &str) -> Result<Resolution, String> {
+        match self.resolver.resolve(dir, specifier) {
+            Ok(resolved_path) => {
+                self.record_file(resolved_path.path());
+                if let Some(package_json) = resolved_path.package_json() {
+                    self.record_file(&package_json.path);
+                }
+                Ok(resolved_path)
+            }
+            Err(e) => {
+                self.record(SourceDependency::Unresolved {
+                    directory: dir.clone(),
+                    specifier: specifier.to_string(),
+                });
+                Err(e.to_string())
+            }
         }
     }
 }
@@ -1,10 +1,21 @@
 use ignore::{DirEntry, WalkBuilder};
 use petgraph::{algo::toposort, graph::NodeIndex, Graph};
+use rayon::prelude::*;
 use spinne_logger::Logger;
-use std::path::PathBuf;
-
-use super::project_types::{ConsumerProject, Project, SourceProject};
-use crate::{graph::ComponentRegistry, package_json::PackageJson};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    project_types::{ConsumerProject, Project, SourceProject},
+    workspace_members::find_workspace_members,
+};
+use crate::{
+    cache::{AnalysisCache, CACHE_DIR},
+    graph::ComponentRegistry,
+    package_json::PackageJson,
+};
 
 /// Represents a workspace containing multiple projects.
 /// A workspace is a directory that contains multiple projects and holds a shared component registry
@@ -13,6 +24,10 @@ pub struct Workspace {
     projects: Vec<Box<dyn Project>>,
     graph: Graph<usize, ()>,
     component_registry: ComponentRegistry,
+    /// Number of threads used for the analysis, `0` uses all cores
+    jobs: usize,
+    /// Directory of the analysis cache, `None` if caching is disabled
+    cache_dir: Option<PathBuf>,
 }
 
 impl Workspace {
@@ -23,9 +38,27 @@ impl Workspace {
             projects: Vec::new(),
             graph: Graph::new(),
             component_registry: ComponentRegistry::new(),
+            jobs: 0,
+            cache_dir: None,
         }
     }
 
+    /// Sets the number of threads used for the analysis. `0` uses all cores.
+    pub fn set_jobs(&mut self, jobs: usize) {
+        self.jobs = jobs;
+    }
+
+    /// Caches the analysis results of unchanged files in `.spinne-cache` in the workspace root.
+    /// Has to be called before the projects are discovered.
+    pub fn enable_cache(&mut self) {
+        self.cache_dir = Some(self.workspace_root.join(CACHE_DIR));
+    }
+
+    /// Removes the analysis cache of the workspace
+    pub fn clear_cache(&self) -> std::io::Result<()> {
+        AnalysisCache::clear(&self.workspace_root.join(CACHE_DIR))
+    }
+
     /// Gets a reference to the component registry
     pub fn get_component_registry(&self) -> &ComponentRegistry {
         &self.component_registry
@@ -64,7 +97,9 @@ impl Workspace {
         self.classify_projects(discovered_projects);
     }
 
-    /// Discovers a single project and adds it to the list of discovered projects
+    /// Discovers the projects of a directory and adds them to the list of discovered projects.
+    /// Repository roots and the workspace root are checked for member packages (`workspaces`,
+    /// `pnpm-workspace.yaml`, `lerna.json` or nx). If there are none, the directory itself is a project.
     fn discover_project(&self, entry: &DirEntry, discovered_projects: &mut Vec<(PathBuf, String)>) {
         let path = entry.path();
 
@@ -73,25 +108,49 @@ impl Workspace {
             return;
         }
 
-        // Check if this is a .git directory
-        if path.file_name().map_or(false, |name| name == ".git") {
-            let project_root = path.parent().unwrap_or(path).to_path_buf();
-
-            // Check if package.json exists in the project root
-            let package_json_path = project_root.join("package.json");
-            if package_json_path.exists() {
-                // Read the project name from package.json
-                if let Some(package_json) = PackageJson::read(&package_json_path, false) {
-                    if let Some(project_name) = package_json.name {
-                        Logger::info(&format!(
-                            "Found project at: {} ({})",
-                            project_root.display(),
-                            project_name
-                        ));
-                        discovered_projects.push((project_root, project_name));
-                    }
+        // Members of a monorepo don't need their own .git directory
+        if path != self.workspace_root && !path.join(".git").exists() {
+            return;
+        }
+
+        if !path.join("package.json").exists() {
+            return;
+        }
+
+        match find_workspace_members(path) {
+            Some(members) => {
+                Logger::info(&format!(
+                    "Found {} workspace members in: {}",
+                    members.len(),
+                    path.display()
+                ));
+                for member in members {
+                    Self::add_project(&member, discovered_projects);
                 }
             }
+            None => Self::add_project(path, discovered_projects),
+        }
+    }
+
+    /// Adds a project to the list of discovered projects if it has a name and wasn't discovered yet
+    fn add_project(project_root: &Path, discovered_projects: &mut Vec<(PathBuf, String)>) {
+        if discovered_projects
+            .iter()
+            .any(|(root, _)| root == project_root)
+        {
+            return;
+        }
+
+        // Read the project name from package.json
+        if let Some(package_json) = PackageJson::read(&project_root.join("package.json"), false) {
+            if let Some(project_name) = package_json.name {
+                Logger::info(&format!(
+                    "Found project at: {} ({})",
+                    project_root.display(),
+                    project_name
+                ));
+                discovered_projects.push((project_root.to_path_buf(), project_name));
+            }
         }
     }
 
@@ -109,14 +168,16 @@ impl Workspace {
             let node_idx = temp_graph.add_node(i);
             project_indices.insert(project_name.clone(), node_idx);
 
-            // Create a source project with a reference to the workspace's component registry
-            let source_project =
-                SourceProject::new(project_root.clone(), &mut self.component_registry);
+            // Create a source project, its components are merged into the workspace after traversal
+            let mut source_project = SourceProject::new(project_root.clone());
+            if let Some(cache_dir) = &self.cache_dir {
+                source_project = source_project.with_cache_dir(cache_dir.clone());
+            }
             self.projects.push(Box::new(source_project));
         }
 
         // Second pass: add edges based on dependencies and identify consumer projects
-        for (i, (project_root, project_name)) in discovered_projects.iter().enumerate() {
+        for (i, (project_root, _project_name)) in discovered_projects.iter().enumerate() {
             // Read dependencies from package.json
             if let Some(package_json) = PackageJson::read(&project_root.join("package.json"), true)
             {
@@ -136,7 +197,7 @@ impl Workspace {
         // Third pass: identify consumer projects and update the graph
         let mut consumer_indices = Vec::new();
 
-        for (i, (project_root, project_name)) in discovered_projects.iter().enumerate() {
+        for (i, (project_root, _project_name)) in discovered_projects.iter().enumerate() {
             // Check if this project has any outgoing edges (depends on other projects)
             let node_idx = NodeIndex::new(i);
             if temp_graph
@@ -148,8 +209,10 @@ impl Workspace {
                 consumer_indices.push(i);
 
                 // Replace the source project with a consumer project
-                let mut consumer_project =
-                    ConsumerProject::new(project_root.clone(), &mut self.component_registry);
+                let mut consumer_project = ConsumerProject::new(project_root.clone());
+                if let Some(cache_dir) = &self.cache_dir {
+                    consumer_project = consumer_project.with_cache_dir(cache_dir.clone());
+                }
 
                 // Add source projects that this consumer depends on
                 if let Some(package_json) =
@@ -185,41 +248,108 @@ impl Workspace {
         ));
     }
 
-    /// Traverses all discovered projects to analyze their components in dependency order
-    pub fn traverse_projects(&mut self, exclude: &Vec<String>, include: &Vec<String>) {
+    /// Traverses all discovered projects to analyze their components in dependency order.
+    /// Projects that don't depend on each other are traversed in parallel.
+    pub fn traverse_projects(&mut self, exclude: &[String], include: &[String]) {
         // Build dependency graph
         let dep_graph = self.build_dependency_graph();
         self.graph = dep_graph;
 
         // Get topological sort
-        match toposort(&self.graph, None) {
+        let levels = match toposort(&self.graph, None) {
             Ok(sorted_projects) => {
                 Logger::info("Traversing projects in dependency order");
-                // Traverse in reverse order to ensure dependencies are processed first
-                for node_idx in sorted_projects.iter().rev() {
-                    let project_idx = self.graph[*node_idx];
-                    let project = &mut self.projects[project_idx];
-                    Logger::info(&format!("Traversing project: {}", project.get_name()));
-                    project.traverse(exclude, include);
-                }
+                self.project_levels(&sorted_projects)
             }
             Err(_) => {
                 Logger::warn(
                     "Circular dependencies detected, falling back to sequential traversal",
                 );
-                // Fallback to regular traversal
-                for project in &mut self.projects {
-                    project.traverse(exclude, include);
+                (0..self.projects.len()).map(|i| vec![i]).collect()
+            }
+        };
+
+        let mut traverse = || {
+            for level in &levels {
+                let registry = &self.component_registry;
+                self.projects
+                    .par_iter_mut()
+                    .enumerate()
+                    .filter(|(i, _)| level.contains(i))
+                    .for_each(|(_, project)| {
+                        Logger::info(&format!("Traversing project: {}", project.get_name()));
+                        project.traverse(registry, exclude, include);
+                    });
+
+                // Merge in the order of the projects so the result doesn't depend on scheduling
+                for project_idx in level {
+                    self.component_registry
+                        .merge(self.projects[*project_idx].get_component_graph());
                 }
             }
+        };
+
+        match rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs)
+            .build()
+        {
+            Ok(pool) => pool.install(traverse),
+            Err(e) => {
+                Logger::error(&format!("Failed to create thread pool: {}", e));
+                traverse();
+            }
         }
     }
 
+    /// Groups projects into levels that only depend on projects of earlier levels.
+    /// `sorted_projects` is the topological order of the dependency graph.
+    fn project_levels(&self, sorted_projects: &[NodeIndex]) -> Vec<Vec<usize>> {
+        let mut node_levels: HashMap<NodeIndex, usize> = HashMap::new();
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+
+        // Dependencies come after their dependents in the topological order
+        for node_idx in sorted_projects.iter().rev() {
+            let level = self
+                .graph
+                .neighbors_directed(*node_idx, petgraph::Direction::Outgoing)
+                .filter_map(|dep_idx| node_levels.get(&dep_idx))
+                .map(|dep_level| dep_level + 1)
+                .max()
+                .unwrap_or(0);
+            node_levels.insert(*node_idx, level);
+
+            if levels.len() <= level {
+                levels.resize(level + 1, Vec::new());
+            }
+            levels[level].push(self.graph[*node_idx]);
+        }
+
+        for level in &mut levels {
+            level.sort();
+        }
+
+        levels
+    }
+
     /// Gets a reference to all discovered projects
     pub fn get_projects(&self) -> &Vec<Box<dyn Project>> {
         &self.projects
     }
 
+    /// Gets the directory of each project relative to the workspace root, by project name
+    pub fn get_project_paths(&self) -> HashMap<String, PathBuf> {
+        self.projects
+            .iter()
+            .map(|project| {
+                let path = project
+                    .get_root()
+                    .strip_prefix(&self.workspace_root)
+                    .unwrap_or(project.get_root());
+                (project.get_name().to_string(), path.to_path_buf())
+            })
+            .collect()
+    }
+
     fn build_dependency_graph(&self) -> Graph<usize, ()> {
         let mut graph = Graph::<usize, ()>::new();
 
@@ -260,7 +390,10 @@ impl Workspace {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util::test_utils;
+    use crate::{
+        analyze::component::{PropValueKind, SourceLocation},
+        util::test_utils,
+    };
 
     #[test]
     fn test_workspace_discovery() {
@@ -293,6 +426,107 @@ mod tests {
         assert_eq!(workspace.get_projects().len(), 2);
     }
 
+    #[test]
+    fn test_monorepo_discovery() {
+        let temp_dir = test_utils::create_mock_project(&vec![
+            (".git/HEAD", "ref: refs/heads/main"),
+            (
+                "package.json",
+                r#"{"name": "monorepo", "workspaces": ["packages/*"]}"#,
+            ),
+            ("packages/ui/package.json", r#"{"name": "ui"}"#),
+            (
+                "packages/ui/src/Button.tsx",
+                r#"export const Button = () => <button />;"#,
+            ),
+            (
+                "packages/app/package.json",
+                r#"{"name": "app", "dependencies": {"ui": "1.0.0"}}"#,
+            ),
+            (
+                "packages/app/src/App.tsx",
+                r#"
+                import { Button } from 'ui';
+
+                export const App = () => <Button />;
+                "#,
+            ),
+        ]);
+
+        let mut workspace = Workspace::new(temp_dir.path().to_path_buf());
+        workspace.discover_projects();
+
+        let mut names: Vec<_> = workspace
+            .get_projects()
+            .iter()
+            .map(|project| project.get_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["app", "ui"]);
+
+        workspace.traverse_projects(&[], &[]);
+
+        let registry = workspace.get_component_registry();
+        assert!(registry.find_component("Button", "ui").is_some());
+        assert!(registry.find_component("App", "app").is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_workspace_package() {
+        let temp_dir = test_utils::create_mock_project(&vec![
+            (".git/HEAD", "ref: refs/heads/main"),
+            (
+                "package.json",
+                r#"{"name": "monorepo", "workspaces": ["packages/*"]}"#,
+            ),
+            (
+                "packages/ui/package.json",
+                r#"{"name": "@acme/ui", "main": "src/index.ts"}"#,
+            ),
+            (
+                "packages/ui/src/index.ts",
+                r#"export { Button } from './Button';"#,
+            ),
+            (
+                "packages/ui/src/Button.tsx",
+                r#"export const Button = () => <button />;"#,
+            ),
+            (
+                "packages/app/package.json",
+                r#"{"name": "app", "dependencies": {"@acme/ui": "1.0.0"}}"#,
+            ),
+            (
+                "packages/app/src/App.tsx",
+                r#"
+                import { Button } from '@acme/ui';
+
+                export const App = () => <Button />;
+                "#,
+            ),
+        ]);
+        // package managers link workspace packages into node_modules
+        let node_modules = temp_dir.path().join("packages/app/node_modules/@acme");
+        std::fs::create_dir_all(&node_modules).unwrap();
+        std::os::unix::fs::symlink(temp_dir.path().join("packages/ui"), node_modules.join("ui"))
+            .unwrap();
+
+        let mut workspace = Workspace::new(temp_dir.path().to_path_buf());
+        workspace.discover_projects();
+        workspace.traverse_projects(&[], &[]);
+
+        let registry = workspace.get_component_registry();
+        let button = registry.find_component("Button", "@acme/ui").unwrap();
+        let app = registry.find_component("App", "app").unwrap();
+        let dependencies: Vec<String> = registry
+            .get_dependencies(&app.node.id)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(dependencies, vec![button.node.id.clone()]);
+        assert_eq!(registry.get_project_components("app").len(), 1);
+    }
+
     #[test]
     fn test_project_sorting() {
         let temp_dir = test_utils::create_mock_project(&vec![
@@ -335,6 +569,87 @@ mod tests {
         assert_eq!(graph.node_weight(2.into()), Some(&2));
     }
 
+    #[test]
+    fn test_parallel_traversal() {
+        let temp_dir = test_utils::create_mock_project(&vec![
+            ("icons/.git/HEAD", "ref: refs/heads/main"),
+            ("icons/package.json", r#"{"name": "icons"}"#),
+            (
+                "icons/src/Icon.tsx",
+                r#"export const Icon = () => <svg />;"#,
+            ),
+            ("ui/.git/HEAD", "ref: refs/heads/main"),
+            ("ui/package.json", r#"{"name": "ui"}"#),
+            (
+                "ui/src/Button.tsx",
+                r#"export const Button = () => <button />;"#,
+            ),
+            ("app/.git/HEAD", "ref: refs/heads/main"),
+            (
+                "app/package.json",
+                r#"{"name": "app", "dependencies": {"ui": "1.0.0", "icons": "1.0.0"}}"#,
+            ),
+            (
+                "app/src/App.tsx",
+                r#"
+                import { Button } from 'ui';
+                import { Icon } from 'icons';
+
+                export const App = () => <Button><Icon /></Button>;
+                "#,
+            ),
+            (
+                "app/src/Page.tsx",
+                r#"
+                import { Button } from 'ui';
+
+                export const Page = () => <><Button variant="primary" /><Button /></>;
+                "#,
+            ),
+        ]);
+
+        let traverse = |jobs: usize| {
+            let mut workspace = Workspace::new(temp_dir.path().to_path_buf());
+            workspace.set_jobs(jobs);
+            workspace.discover_projects();
+            workspace.traverse_projects(&[], &[]);
+
+            let levels = workspace.project_levels(&toposort(&workspace.graph, None).unwrap());
+            let level_names: Vec<Vec<&str>> = levels
+                .iter()
+                .map(|level| {
+                    let mut names: Vec<_> = level
+                        .iter()
+                        .map(|i| workspace.get_projects()[*i].get_name())
+                        .collect();
+                    names.sort();
+                    names
+                })
+                .collect();
+            assert_eq!(level_names, vec![vec!["icons", "ui"], vec!["app"]]);
+
+            let registry = workspace.get_component_registry();
+            let button = registry.find_component("Button", "ui").unwrap();
+            let page = registry.find_component("Page", "app").unwrap();
+            let edges = registry.get_dependencies(&page.node.id);
+            assert_eq!(edges.len(), 1);
+            assert_eq!(edges[0].0, button.node.id);
+
+            let app = registry.find_component("App", "app").unwrap();
+            let mut dependencies: Vec<_> = registry
+                .get_dependencies(&app.node.id)
+                .into_iter()
+                .map(|(id, _)| registry.get_component(&id).unwrap().node.name.clone())
+                .collect();
+            dependencies.sort();
+            assert_eq!(dependencies, vec!["Button", "Icon"]);
+
+            (button.node.props.clone(), edges[0].1.usages.clone())
+        };
+
+        assert_eq!(traverse(1), traverse(4));
+    }
+
     #[test]
     fn test_source_consumer_component_flow() {
         let temp_dir = test_utils::create_mock_project(&vec![
@@ -445,7 +760,6 @@ mod tests {
             "App component should exist in consumer project"
         );
 
-
         // Verify the connection between App and Button
         if let Some(app_info) = app_component {
             let app_deps = registry.get_dependencies(&app_info.node.id);
@@ -457,11 +771,43 @@ mod tests {
                 Some("source-lib".to_string()),
                 "Button dependency should reference source-lib project"
             );
+            assert_eq!(
+                button_dep.1.usages[0].location,
+                SourceLocation {
+                    line: 7,
+                    column: 28
+                }
+            );
         }
 
+        // Button comes from another project of the workspace, <button> is a native element
+        let consumer_adoption = registry.get_adoption("consumer-app").unwrap().total();
+        assert_eq!(
+            (consumer_adoption.workspace, consumer_adoption.total()),
+            (1, 1)
+        );
+        let source_adoption = registry.get_adoption("source-lib").unwrap().total();
+        assert_eq!((source_adoption.intrinsic, source_adoption.total()), (1, 1));
+
         if let Some(button_info) = button_component {
             assert_eq!(button_info.node.props.get("label"), Some(&1));
+            assert_eq!(button_info.node.intrinsic_elements.get("button"), Some(&1));
             assert_eq!(button_info.node.props.get("onClick"), Some(&1));
+
+            let declared: Vec<&str> = button_info
+                .node
+                .declared_props
+                .iter()
+                .map(|prop| prop.name.as_str())
+                .collect();
+            assert_eq!(declared, vec!["label", "onClick"]);
+            assert_eq!(
+                button_info.node.location,
+                Some(SourceLocation {
+                    line: 9,
+                    column: 24
+                })
+            );
         }
     }
 
@@ -487,7 +833,10 @@ mod tests {
                 };
                 "#,
             ),
-            ("source-lib/src/components/index.ts", r#"export * from './Button';"#),
+            (
+                "source-lib/src/components/index.ts",
+                r#"export * from './Button';"#,
+            ),
             // Consumer project that uses the Button component in two files
             ("consumer-app/.git/HEAD", "ref: refs/heads/main"),
             (
@@ -538,4 +887,185 @@ mod tests {
         assert_eq!(button.node.props.get("onClick"), Some(&2));
         assert_eq!(button.node.props.get("color"), Some(&1));
     }
+
+    #[test]
+    fn test_edge_multiplicity() {
+        let temp_dir = test_utils::create_mock_project(&vec![
+            ("app/.git/HEAD", "ref: refs/heads/main"),
+            ("app/package.json", r#"{"name": "app"}"#),
+            (
+                "app/src/Toolbar.tsx",
+                r#"
+                import { Button } from './Button';
+
+                export const Toolbar = () => {
+                    return <div>
+                        <Button variant="primary" label="Save" />
+                        <Button variant="secondary" label="Cancel" />
+                        <Button label="Help" />
+                    </div>;
+                };
+                "#,
+            ),
+            (
+                "app/src/Button.tsx",
+                r#"
+                export const Button = ({ label, variant }) => <button className={variant}>{label}</button>;
+                "#,
+            ),
+        ]);
+
+        let mut workspace = Workspace::new(temp_dir.path().to_path_buf());
+        workspace.discover_projects();
+        workspace.traverse_projects(&[], &[]);
+
+        let registry = workspace.get_component_registry();
+        let toolbar = registry
+            .find_component("Toolbar", "app")
+            .expect("Toolbar should exist");
+
+        let dependencies = registry.get_dependencies(&toolbar.node.id);
+        assert_eq!(dependencies.len(), 1);
+
+        let edge = dependencies[0].1;
+        assert_eq!(edge.count, 3);
+        assert_eq!(edge.props.get("label"), Some(&3));
+        assert_eq!(edge.props.get("variant"), Some(&2));
+        assert_eq!(edge.usages.len(), 3);
+
+        let button = registry
+            .find_component("Button", "app")
+            .expect("Button should exist");
+        let variants = &button.node.prop_values["variant"];
+        assert_eq!(variants.values.get("primary"), Some(&1));
+        assert_eq!(variants.values.get("secondary"), Some(&1));
+        assert_eq!(variants.kinds.get(&PropValueKind::String), Some(&2));
+    }
+
+    #[test]
+    fn test_package_and_local_component_with_the_same_name() {
+        let temp_dir = test_utils::create_mock_project(&vec![
+            ("app/.git/HEAD", "ref: refs/heads/main"),
+            ("app/package.json", r#"{"name": "app"}"#),
+            (
+                "app/node_modules/@acme/ui/package.json",
+                r#"{"name": "@acme/ui", "version": "1.0.0", "main": "index.js"}"#,
+            ),
+            (
+                "app/node_modules/@acme/ui/index.js",
+                "export const Button = () => null;",
+            ),
+            (
+                "app/src/App.tsx",
+                r#"
+                import { Button as UIButton } from '@acme/ui';
+                import { Button } from './Local';
+
+                export const App = () => <div><UIButton /><Button /><Button /></div>;
+                "#,
+            ),
+            (
+                "app/src/Local.tsx",
+                "export const Button = () => <button />;",
+            ),
+        ]);
+
+        let mut workspace = Workspace::new(temp_dir.path().to_path_buf());
+        workspace.discover_projects();
+        workspace.traverse_projects(&[], &[]);
+
+        let registry = workspace.get_component_registry();
+        let app = registry.find_component("App", "app").unwrap();
+        let mut dependencies: Vec<(String, usize)> = registry
+            .get_dependencies(&app.node.id)
+            .into_iter()
+            .map(|(id, edge)| {
+                let button = registry.get_component(&id).unwrap();
+                assert_eq!(button.node.name, "Button");
+                (button.node.file_path.display().to_string(), edge.count)
+            })
+            .collect();
+        dependencies.sort();
+
+        assert_eq!(
+            dependencies,
+            vec![
+                ("@acme/ui".to_string(), 1),
+                ("app/src/Local.tsx".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entry_points() {
+        let temp_dir = test_utils::create_mock_project(&vec![
+            ("ui/.git/HEAD", "ref: refs/heads/main"),
+            ("ui/package.json", r#"{"name": "ui"}"#),
+            (
+                "ui/spinne.json",
+                r#"{"entry_points": ["src/index.ts", "src/main.tsx"]}"#,
+            ),
+            ("ui/src/index.ts", r#"export { Button } from './Button';"#),
+            (
+                "ui/src/main.tsx",
+                r#"
+                import { createRoot } from 'react-dom/client';
+                import { App } from './App';
+
+                createRoot(document.getElementById('root')).render(<App />);
+                "#,
+            ),
+            (
+                "ui/src/App.tsx",
+                r#"
+                import { Card } from './Card';
+
+                export const App = () => <Card />;
+                "#,
+            ),
+            ("ui/src/Card.tsx", r#"export const Card = () => <div />;"#),
+            (
+                "ui/src/Button.tsx",
+                r#"
+                import { Icon } from './Icon';
+
+                export const Button = () => <button><Icon /></button>;
+                "#,
+            ),
+            ("ui/src/Icon.tsx", r#"export const Icon = () => <svg />;"#),
+            (
+                "ui/src/Legacy.tsx",
+                r#"
+                import { Icon } from './Icon';
+
+                export const Legacy = () => <Icon />;
+                "#,
+            ),
+        ]);
+
+        let mut workspace = Workspace::new(temp_dir.path().to_path_buf());
+        workspace.discover_projects();
+        workspace.traverse_projects(&[], &[]);
+
+        let registry = workspace.get_component_registry();
+        let node = |name: &str| {
+            &registry
+                .find_component(name, "ui")
+                .unwrap_or_else(|| panic!("{} should exist", name))
+                .node
+        };
+
+        for name in ["App", "Card", "Button", "Icon"] {
+            assert_eq!(node(name).reachable, Some(true), "{} is reachable", name);
+        }
+        assert_eq!(node("Legacy").reachable, Some(false));
+
+        assert!(node("Button").public_api);
+        assert!(!node("App").public_api);
+        assert!(!node("Icon").public_api);
+
+        let unreachable = registry.get_unreachable_components("ui");
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(unreachable[0].node.name, "Legacy");
+    }
 }
@@ -0,0 +1,339 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Pattern;
+use ignore::WalkBuilder;
+use serde_json::Value;
+use spinne_logger::Logger;
+
+use crate::package_json::PackageJson;
+
+/// Finds the member packages of a monorepo root.
+/// Members are declared through the `workspaces` field of `package.json`, `pnpm-workspace.yaml`,
+/// the `packages` field of `lerna.json` or, for nx, the `project.json` files of the workspace.
+/// Returns `None` if the directory does not declare any members.
+/// Only members that contain a `package.json` are returned.
+pub fn find_workspace_members(root: &Path) -> Option<Vec<PathBuf>> {
+    let mut patterns = Vec::new();
+    let mut is_workspace_root = false;
+
+    let package_json_path = root.join("package.json");
+    if let Some(workspaces) = package_json_path
+        .is_file()
+        .then(|| PackageJson::read(&package_json_path, false))
+        .flatten()
+        .and_then(|package_json| package_json.workspaces)
+    {
+        is_workspace_root = true;
+        patterns.extend(workspaces);
+    }
+
+    if let Some(packages) = read_pnpm_workspace(&root.join("pnpm-workspace.yaml")) {
+        is_workspace_root = true;
+        patterns.extend(packages);
+    }
+
+    if let Some(packages) = read_lerna_packages(&root.join("lerna.json")) {
+        is_workspace_root = true;
+        patterns.extend(packages);
+    }
+
+    let mut members = expand_patterns(root, &patterns);
+
+    if root.join("nx.json").is_file() {
+        is_workspace_root = true;
+        members.extend(find_nx_projects(root));
+    }
+
+    if !is_workspace_root {
+        return None;
+    }
+
+    members.sort();
+    members.dedup();
+
+    Some(members)
+}
+
+/// Reads the `packages` of a `pnpm-workspace.yaml` file
+fn read_pnpm_workspace(path: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(path).ok()?;
+
+    Some(parse_pnpm_packages(&content))
+}
+
+/// Parses the `packages` list of a `pnpm-workspace.yaml` file.
+/// Only the `packages` key is read, written either as a block sequence (`- 'apps/*'`) or as a
+/// flow sequence (`['apps/*']`), so no full YAML parser is needed. Other keys are ignored.
+fn parse_pnpm_packages(content: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // a top level key, e.g. `packages:` or `catalog:`
+        if !line.starts_with([' ', '\t', '-']) {
+            let Some(value) = trimmed.strip_prefix("packages:") else {
+                in_packages = false;
+                continue;
+            };
+
+            let value = strip_yaml_comment(value);
+            if let Some(list) = value
+                .strip_prefix('[')
+                .and_then(|value| value.strip_suffix(']'))
+            {
+                packages.extend(
+                    list.split(',')
+                        .map(unquote_yaml)
+                        .filter(|package| !package.is_empty()),
+                );
+            }
+            in_packages = value.is_empty();
+            continue;
+        }
+
+        if in_packages {
+            if let Some(item) = trimmed.strip_prefix('-') {
+                let package = unquote_yaml(strip_yaml_comment(item));
+                if !package.is_empty() {
+                    packages.push(package);
+                }
+            }
+        }
+    }
+
+    packages
+}
+
+/// Removes a trailing ` # comment` from a YAML value that is not inside quotes
+fn strip_yaml_comment(value: &str) -> &str {
+    let value = value.trim();
+    if value.starts_with(['\'', '"']) {
+        return value;
+    }
+
+    match value.find(" #") {
+        Some(index) => value[..index].trim(),
+        None => value,
+    }
+}
+
+/// Removes the quotes of a single or double quoted YAML scalar
+fn unquote_yaml(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['\'', '"'] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.find(quote).map(|end| &value[..end]))
+        {
+            return inner.to_string();
+        }
+    }
+
+    value.to_string()
+}
+
+/// Reads the `packages` of a `lerna.json` file.
+/// Lerna falls back to the `workspaces` of `package.json` if `packages` is not set.
+fn read_lerna_packages(path: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(path).ok()?;
+
+    match serde_json::from_str::<Value>(&content) {
+        Ok(value) => value
+            .get("packages")
+            .and_then(|packages| packages.as_array())
+            .map(|packages| {
+                packages
+                    .iter()
+                    .filter_map(|package| package.as_str())
+                    .map(ToString::to_string)
+                    .collect()
+            }),
+        Err(e) => {
+            Logger::error(&format!("Failed to parse {}: {}", path.display(), e));
+            None
+        }
+    }
+}
+
+/// Expands workspace globs relative to the root. Patterns starting with `!` exclude members.
+fn expand_patterns(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let (excludes, includes): (Vec<_>, Vec<_>) = patterns
+        .iter()
+        .map(|pattern| pattern.trim_start_matches("./"))
+        .partition(|pattern| pattern.starts_with('!'));
+
+    let excludes: Vec<Pattern> = excludes
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern[1..].trim_start_matches("./")).ok())
+        .collect();
+
+    let mut members = Vec::new();
+
+    for pattern in includes {
+        let full_pattern = root.join(pattern);
+        let paths = match glob::glob(&full_pattern.to_string_lossy()) {
+            Ok(paths) => paths,
+            Err(e) => {
+                Logger::warn(&format!("Invalid workspace pattern {}: {}", pattern, e));
+                continue;
+            }
+        };
+
+        for path in paths.flatten() {
+            if !path.join("package.json").is_file() || is_node_module(&path) {
+                continue;
+            }
+
+            let relative_path = path.strip_prefix(root).unwrap_or(&path);
+            if excludes
+                .iter()
+                .any(|exclude| exclude.matches_path(relative_path))
+            {
+                continue;
+            }
+
+            members.push(path);
+        }
+    }
+
+    members
+}
+
+/// Finds the directories of an nx workspace that contain a `project.json` file
+fn find_nx_projects(root: &Path) -> Vec<PathBuf> {
+    let walker = WalkBuilder::new(root)
+        .git_ignore(true)
+        .filter_entry(|entry| entry.file_name() != "node_modules")
+        .build();
+
+    walker
+        .flatten()
+        .filter(|entry| entry.file_name() == "project.json")
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .filter(|path| path != root)
+        .filter(|path| {
+            let has_package_json = path.join("package.json").is_file();
+            if !has_package_json {
+                Logger::warn(&format!(
+                    "Skipping nx project without package.json: {}",
+                    path.display()
+                ));
+            }
+            has_package_json
+        })
+        .collect()
+}
+
+fn is_node_module(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == "node_modules")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::test_utils::create_mock_project;
+
+    use super::*;
+
+    #[test]
+    fn test_package_json_workspaces() {
+        let temp_dir = create_mock_project(&vec![
+            (
+                "package.json",
+                r#"{"name": "root", "workspaces": ["packages/*", "!packages/internal"]}"#,
+            ),
+            ("packages/ui/package.json", r#"{"name": "ui"}"#),
+            ("packages/app/package.json", r#"{"name": "app"}"#),
+            ("packages/internal/package.json", r#"{"name": "internal"}"#),
+            ("packages/docs/README.md", "no package"),
+        ]);
+        let root = temp_dir.path();
+
+        assert_eq!(
+            find_workspace_members(root),
+            Some(vec![root.join("packages/app"), root.join("packages/ui")])
+        );
+    }
+
+    #[test]
+    fn test_pnpm_workspace() {
+        let temp_dir = create_mock_project(&vec![
+            ("package.json", r#"{"name": "root"}"#),
+            (
+                "pnpm-workspace.yaml",
+                "packages:\n  - 'apps/*'\n  - \"libs/**\"\n",
+            ),
+            ("apps/web/package.json", r#"{"name": "web"}"#),
+            ("libs/shared/ui/package.json", r#"{"name": "ui"}"#),
+        ]);
+        let root = temp_dir.path();
+
+        assert_eq!(
+            find_workspace_members(root),
+            Some(vec![root.join("apps/web"), root.join("libs/shared/ui")])
+        );
+    }
+
+    #[test]
+    fn test_parse_pnpm_packages() {
+        let content = r#"
+# workspace packages
+packages:
+  - 'apps/*' # applications
+  - "libs/**"
+  - packages/* # shared
+  - '!**/test/**'
+
+catalog:
+  - not-a-package
+"#;
+        assert_eq!(
+            parse_pnpm_packages(content),
+            vec!["apps/*", "libs/**", "packages/*", "!**/test/**"]
+        );
+
+        assert_eq!(
+            parse_pnpm_packages("packages:\n- apps/*\nonlyBuiltDependencies:\n- esbuild\n"),
+            vec!["apps/*"]
+        );
+        assert_eq!(
+            parse_pnpm_packages("packages: ['apps/*', \"libs/*\"]\n"),
+            vec!["apps/*", "libs/*"]
+        );
+        assert!(parse_pnpm_packages("catalog:\n  react: ^18\n").is_empty());
+    }
+
+    #[test]
+    fn test_lerna_and_nx() {
+        let temp_dir = create_mock_project(&vec![
+            ("package.json", r#"{"name": "root"}"#),
+            ("lerna.json", r#"{"packages": ["modules/*"]}"#),
+            ("nx.json", "{}"),
+            ("modules/button/package.json", r#"{"name": "button"}"#),
+            ("apps/admin/project.json", r#"{"name": "admin"}"#),
+            ("apps/admin/package.json", r#"{"name": "admin"}"#),
+            ("apps/config/project.json", r#"{"name": "config"}"#),
+        ]);
+        let root = temp_dir.path();
+
+        assert_eq!(
+            find_workspace_members(root),
+            Some(vec![root.join("apps/admin"), root.join("modules/button")])
+        );
+    }
+
+    #[test]
+    fn test_no_workspace() {
+        let temp_dir = create_mock_project(&vec![("package.json", r#"{"name": "root"}"#)]);
+
+        assert_eq!(find_workspace_members(temp_dir.path()), None);
+    }
+}
@@ -5,13 +5,16 @@ use spinne_logger::Logger;
 /// Check if a string is in PascalCase.
 /// We can only check here if the first character is uppercase because we don't know the context of the string.
 pub fn is_pascal_case(name: &str) -> bool {
-    name.chars().next().map_or(false, |c| c.is_uppercase())
+    name.chars().next().is_some_and(|c| c.is_uppercase())
 }
 
 /// Reduce a path to the node module name.
+/// Scoped packages keep their scope, e.g. `@acme/ui`.
 pub fn reduce_to_node_module_name(path: &str) -> String {
-    let last = path.split("node_modules/").last().unwrap().to_string();
-    last.split('/').next().unwrap().to_string()
+    let last = path.split("node_modules/").last().unwrap();
+    let segments = if last.starts_with('@') { 2 } else { 1 };
+
+    last.split('/').take(segments).collect::<Vec<_>>().join("/")
 }
 
 /// Replace an absolute path with a project name.
@@ -106,5 +109,15 @@ mod tests {
             reduce_to_node_module_name("node_modules/material-ui/index.tsx"),
             "material-ui"
         );
+        assert_eq!(
+            reduce_to_node_module_name("/app/node_modules/@acme/ui/dist/index.d.ts"),
+            "@acme/ui"
+        );
+        assert_eq!(
+            reduce_to_node_module_name(
+                "/app/node_modules/.pnpm/@acme+ui@1.0.0/node_modules/@acme/ui/index.js"
+            ),
+            "@acme/ui"
+        );
     }
 }
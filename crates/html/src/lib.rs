@@ -3,6 +3,7 @@ use std::fs;
 use std::path::Path;
 
 const HTML_TEMPLATE: &str = include_str!("./component-graph.html");
+const DIFF_TEMPLATE: &str = include_str!("./component-diff.html");
 
 /// Generates an HTML report from a component graph.
 /// Uses d3.js to render the graph.
@@ -12,6 +13,11 @@ pub struct HtmlGenerator {
 
 impl HtmlGenerator {
     pub fn new(workspace_data: Value) -> Self {
+        // The graph is drawn from the projects of a versioned report
+        let workspace_data = match workspace_data {
+            Value::Object(mut report) => report.remove("projects").unwrap_or_default(),
+            data => data,
+        };
         // Convert numeric IDs to strings in the JSON data
         let workspace_data = convert_ids_to_strings(workspace_data);
 
@@ -22,6 +28,16 @@ impl HtmlGenerator {
         Self { template }
     }
 
+    /// Generates a report of the changes between two component graphs,
+    /// with added items highlighted in green and removed ones in red
+    pub fn diff(diff_data: Value) -> Self {
+        let template = DIFF_TEMPLATE.replace(
+            "{/* {{DIFF_DATA}} */}",
+            &serde_json::to_string(&diff_data).unwrap_or_default(),
+        );
+        Self { template }
+    }
+
     pub fn save(&self, output_path: &Path) -> std::io::Result<()> {
         fs::write(output_path, self.template.clone())
     }
@@ -95,12 +111,34 @@ mod tests {
           },
         ]);
 
-        let generator = HtmlGenerator::new(project_data);
+        let generator =
+            HtmlGenerator::new(json!({ "schema_version": 1, "projects": project_data }));
 
         assert!(generator.template.contains("App"));
+        assert!(!generator.template.contains("schema_version"));
         assert!(!generator.template.contains("{{GRAPH_DATA}}"));
     }
 
+    #[test]
+    fn test_diff_html_generation() {
+        let diff_data = json!({
+            "added_components": [
+                { "project": "app", "path": "app/src/Card.tsx", "name": "Card", "id": "1" }
+            ],
+            "removed_components": [],
+            "added_edges": [],
+            "removed_edges": [],
+            "prop_changes": [],
+            "added_project_dependencies": [],
+            "removed_project_dependencies": []
+        });
+
+        let generator = HtmlGenerator::diff(diff_data);
+
+        assert!(generator.template.contains("app/src/Card.tsx"));
+        assert!(!generator.template.contains("{{DIFF_DATA}}"));
+    }
+
     #[test]
     fn test_html() {
         let graph_data = json!([
@@ -117,7 +155,14 @@ mod tests {
                   "onClick": 1,
                   "variant": 1,
                   "disabled": 1
-                }
+                },
+                "prop_values": {
+                  "variant": {
+                    "values": { "primary": 3, "<dynamic>": 1 },
+                    "kinds": { "string": 3, "dynamic": 1 }
+                  }
+                },
+                "location": { "line": 12, "column": 8 }
               },
               {
                 "id": "11611080489164640769",
@@ -219,7 +264,18 @@ mod tests {
               {
                 "from": "14300231078674835378",
                 "to": "11611080489164640768",
-                "project_context": "source-lib"
+                "project_context": "source-lib",
+                "count": 2,
+                "props": { "label": 2, "variant": 1 },
+                "usages": [
+                  { "line": 14, "column": 9, "spreads": [], "has_unknown_spread": false },
+                  {
+                    "line": 21,
+                    "column": 13,
+                    "spreads": [{ "source": "rest", "resolved": false }],
+                    "has_unknown_spread": true
+                  }
+                ]
               },
               {
                 "from": "14300231078674835378",